@@ -1,15 +1,42 @@
 //! The `subnetwork` crate provides a set of APIs to work with IP CIDRs in Rust.
-use std::fmt;
-use std::net::AddrParseError;
-use std::net::Ipv4Addr;
-use std::net::Ipv6Addr;
-use std::num::ParseIntError;
-use std::str::FromStr;
+//!
+//! Without the default `std` feature the crate is `no_std` and only needs `alloc`.
+//!
+//! Since 0.5.0 iterating a pool yields all of its addresses, including the network
+//! and broadcast addresses.
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+// the tests print their results and hash pools even without the std feature
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::Hash;
+use core::hash::Hasher;
+use core::net::AddrParseError;
+use core::net::IpAddr;
+use core::net::Ipv4Addr;
+use core::net::Ipv6Addr;
+use core::num::ParseIntError;
+use core::ops::RangeInclusive;
+use core::str::FromStr;
 use thiserror::Error;
 
-const INIT_NEXT_VALUE: u8 = 1;
+const INIT_NEXT_VALUE: u8 = 0;
 const IPV4_LEN: u8 = 32;
 const IPV6_LEN: u8 = 128;
+const IPV4_CIDR_HINT: &str = "expected 'A.B.C.D/prefix' with prefix <= 32";
+const IPV6_CIDR_HINT: &str = "expected 'X:X::X/prefix' with prefix <= 128";
+const IPV4_ADDR_HINT: &str = "expected an IPv4 address";
+const IPV6_ADDR_HINT: &str = "expected an IPv6 address";
+const RANGE_HINT: &str = "expected start address <= end address";
 
 #[derive(Error, Debug)]
 pub enum SubnetworkErrors {
@@ -21,6 +48,15 @@ pub enum SubnetworkErrors {
     ParseIntError(#[from] ParseIntError),
 }
 
+/// Common interface of the IPv4 address sets, whether they are written as a CIDR
+/// (`Ipv4Pool`) or as a range (`CrossIpv4Pool`).
+pub trait CoversAddresses {
+    /// Returns `true` if `address` is in this address set.
+    fn covers(&self, address: Ipv4Addr) -> bool;
+    /// Returns the number of addresses in this address set.
+    fn address_count(&self) -> u64;
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct CrossIpv4Pool {
     start: u32,
@@ -78,52 +114,306 @@ impl CrossIpv4Pool {
             };
             Ok(cip)
         } else {
-            let msg = format!("{}-{}", start, end);
+            let msg = format!("{}-{}, {}", start, end, RANGE_HINT);
             Err(SubnetworkErrors::InvalidInputError { msg })
         }
     }
+    /// Returns the number of addresses in this `CrossIpv4Pool`, both ends included,
+    /// saturating at `usize::MAX` on 32-bit targets.
+    pub fn len(&self) -> usize {
+        usize::try_from(self.end - self.start).map_or(usize::MAX, |n| n.saturating_add(1))
+    }
+    /// Returns all addresses of this `CrossIpv4Pool`, from the start to the end.
+    pub fn to_vec(&self) -> Vec<Ipv4Addr> {
+        (self.start..=self.end).map(Ipv4Addr::from).collect()
+    }
+    /// Returns `true` if the start and the end of this `CrossIpv4Pool` are in
+    /// different `/prefix_len` networks.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::CrossIpv4Pool;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// fn main() {
+    ///     let start = Ipv4Addr::new(192, 168, 1, 1);
+    ///     let end = Ipv4Addr::new(192, 168, 3, 254);
+    ///     let ips = CrossIpv4Pool::new(start, end).unwrap();
+    ///     assert!(ips.spans_multiple(24));
+    /// }
+    /// ```
+    pub fn spans_multiple(&self, prefix_len: u8) -> bool {
+        let shift = (IPV4_LEN - prefix_len.min(IPV4_LEN)) as u32;
+        let mask = u32::MAX.checked_shl(shift).unwrap_or(0);
+        self.start & mask != self.end & mask
+    }
+    /// Returns the ranges covering the addresses which are in exactly one of this
+    /// `CrossIpv4Pool` and `other`, in ascending order.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::CrossIpv4Pool;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// fn main() {
+    ///     let a = CrossIpv4Pool::new(Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(192, 168, 1, 100)).unwrap();
+    ///     let b = CrossIpv4Pool::new(Ipv4Addr::new(192, 168, 1, 50), Ipv4Addr::new(192, 168, 1, 200)).unwrap();
+    ///     for i in a.symmetric_difference(&b) {
+    ///         println!("{}", i);
+    ///     }
+    /// }
+    /// ```
+    pub fn symmetric_difference(&self, other: &CrossIpv4Pool) -> Vec<CrossIpv4Pool> {
+        let range = |start: u32, end: u32| CrossIpv4Pool {
+            start,
+            end,
+            next: start,
+        };
+        if self.end < other.start || other.end < self.start {
+            let (low, high) = if self.start < other.start {
+                (self, other)
+            } else {
+                (other, self)
+            };
+            // adjacent ranges are written as a single one
+            if low.end + 1 == high.start {
+                return vec![range(low.start, high.end)];
+            }
+            return vec![range(low.start, low.end), range(high.start, high.end)];
+        }
+        let mut ret = Vec::new();
+        if self.start != other.start {
+            let start = self.start.min(other.start);
+            let end = self.start.max(other.start) - 1;
+            ret.push(range(start, end));
+        }
+        if self.end != other.end {
+            let start = self.end.min(other.end) + 1;
+            let end = self.end.max(other.end);
+            ret.push(range(start, end));
+        }
+        ret
+    }
+    /// Returns the smallest `Ipv4Pool` containing every address of this `CrossIpv4Pool`.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::CrossIpv4Pool;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// fn main() {
+    ///     let start = Ipv4Addr::new(192, 168, 1, 1);
+    ///     let end = Ipv4Addr::new(192, 168, 3, 254);
+    ///     let ips = CrossIpv4Pool::new(start, end).unwrap();
+    ///     let pool = ips.enclosing_pool();
+    ///     assert_eq!(pool.network(), Ipv4Addr::new(192, 168, 0, 0));
+    ///     assert_eq!(pool.len(), 1024);
+    /// }
+    /// ```
+    pub fn enclosing_pool(&self) -> Ipv4Pool {
+        let start = Ipv4::from_u32(self.start);
+        let end = Ipv4::from_u32(self.end);
+        let prefix_len = start.largest_identical_prefix(end) as u8;
+        Ipv4Pool::build(self.start, prefix_len)
+    }
+    /// Returns the minimal list of aligned `Ipv4Pool`s covering exactly this `CrossIpv4Pool`,
+    /// in ascending order.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::CrossIpv4Pool;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// fn main() {
+    ///     let start = Ipv4Addr::new(192, 168, 1, 1);
+    ///     let end = Ipv4Addr::new(192, 168, 1, 10);
+    ///     let ips = CrossIpv4Pool::new(start, end).unwrap();
+    ///     for i in ips.to_cidrs() {
+    ///         println!("{}", i);
+    ///     }
+    /// }
+    /// ```
+    pub fn to_cidrs(&self) -> Vec<Ipv4Pool> {
+        let mut ret = Vec::new();
+        let mut current = self.start as u64;
+        let end = self.end as u64;
+        while current <= end {
+            // the largest block aligned on current which does not go past the end
+            let mut size = 1u64 << current.trailing_zeros().min(IPV4_LEN as u32);
+            while current + size - 1 > end {
+                size >>= 1;
+            }
+            let prefix_len = IPV4_LEN - size.trailing_zeros() as u8;
+            ret.push(Ipv4Pool::build(current as u32, prefix_len));
+            current += size;
+        }
+        ret
+    }
+    /// Returns the broadcast address of [`CrossIpv4Pool::enclosing_pool`] without building the pool.
+    pub fn enclosing_broadcast(&self) -> Ipv4Addr {
+        let start = Ipv4::from_u32(self.start);
+        let end = Ipv4::from_u32(self.end);
+        let prefix_len = start.largest_identical_prefix(end);
+        let hostmask = u32::MAX.checked_shr(prefix_len).unwrap_or(0);
+        (self.start | hostmask).into()
+    }
+    /// Returns the largest aligned `Ipv4Pool` lying entirely inside this `CrossIpv4Pool`,
+    /// the lowest one if several have the same size.
+    pub fn shrink_to_cidr(&self) -> Option<Ipv4Pool> {
+        (0..=IPV4_LEN).find_map(|prefix_len| {
+            let size = 1u64 << (IPV4_LEN - prefix_len);
+            let first = (self.start as u64).div_ceil(size) * size;
+            if first + size - 1 <= self.end as u64 {
+                Some(Ipv4Pool::build(first as u32, prefix_len))
+            } else {
+                None
+            }
+        })
+    }
+    /// Returns the smallest aligned `Ipv4Pool` containing this `CrossIpv4Pool`,
+    /// the same as [`CrossIpv4Pool::enclosing_pool`].
+    pub fn grow_to_cidr(&self) -> Ipv4Pool {
+        self.enclosing_pool()
+    }
+}
+
+impl CoversAddresses for CrossIpv4Pool {
+    fn covers(&self, address: Ipv4Addr) -> bool {
+        let addr: u32 = address.into();
+        self.start <= addr && addr <= self.end
+    }
+    fn address_count(&self) -> u64 {
+        (self.end - self.start) as u64 + 1
+    }
+}
+
+/// Where the gateway sits in a subnet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GatewayConvention {
+    /// The first host, the network address + 1.
+    First,
+    /// The last host, the broadcast address - 1.
+    Last,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub struct Ipv4Pool {
     prefix: u32,
     mask: u32,
-    next: u32,
-    stop: u32,
+    next: u64,
+    stop: u64,
 }
 
 impl Iterator for Ipv4Pool {
     type Item = Ipv4Addr;
     fn next(&mut self) -> Option<Self::Item> {
         if self.next < self.stop {
-            let ret = self.prefix + self.next;
+            let ret = self.prefix + self.next as u32;
             self.next += 1;
             Some(ret.into())
         } else {
             None
         }
     }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // a /0 holds 2^32 addresses, more than a 32-bit usize
+        match usize::try_from(self.stop - self.next) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+    fn count(self) -> usize {
+        usize::try_from(self.stop - self.next).unwrap_or(usize::MAX)
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // skip the n addresses without walking them
+        self.next = self.next.saturating_add(n as u64).min(self.stop);
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for Ipv4Pool {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.next < self.stop {
+            self.stop -= 1;
+            let ret = self.prefix + self.stop as u32;
+            Some(ret.into())
+        } else {
+            None
+        }
+    }
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.stop = self.stop.saturating_sub(n as u64).max(self.next);
+        self.next_back()
+    }
+}
+
+/// The remaining count is exact whenever it fits in `usize`.
+///
+/// # Panics
+///
+/// `ExactSizeIterator::len` panics on an unconsumed `0.0.0.0/0` pool, such as
+/// `Ipv4Pool::FULL`, on targets where `usize` is 32 bits, since its 2^32 addresses
+/// do not fit. The inherent `Ipv4Pool::len` saturates instead.
+impl ExactSizeIterator for Ipv4Pool {}
+
+impl From<Ipv4Addr> for Ipv4Pool {
+    /// Returns the `/32` pool of this single address.
+    fn from(address: Ipv4Addr) -> Self {
+        Ipv4Pool::build(address.into(), IPV4_LEN)
+    }
 }
 
 impl fmt::Display for Ipv4Pool {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let prefix: Ipv4Addr = self.prefix.into();
-        let mut prefix_len = 0;
-        let mut mask = self.mask;
-        while mask != 0 {
-            mask <<= 1;
-            prefix_len += 1;
-        }
-        let now_addr = self.prefix + self.next;
+        let prefix_len = self.prefix_len();
+        let now_addr = (self.prefix as u64 + self.next) as u32;
         let now_addr: Ipv4Addr = now_addr.into();
         write!(f, "{}/{}, next {}", prefix, prefix_len, now_addr)
     }
 }
 
+impl PartialEq for Ipv4Pool {
+    /// Two pools are equal when they denote the same network, whatever their iteration position.
+    fn eq(&self, other: &Self) -> bool {
+        self.prefix == other.prefix && self.mask == other.mask
+    }
+}
+
+impl Eq for Ipv4Pool {}
+
+impl Hash for Ipv4Pool {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.prefix.hash(state);
+        self.mask.hash(state);
+    }
+}
+
+impl PartialOrd for Ipv4Pool {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ipv4Pool {
+    /// Orders by network address, then by prefix length with the more specific pool last.
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.prefix, self.mask).cmp(&(other.prefix, other.mask))
+    }
+}
+
 impl Ipv4Pool {
+    /// The whole IPv4 address space, `0.0.0.0/0`.
+    pub const FULL: Ipv4Pool = Ipv4Pool {
+        prefix: 0,
+        mask: 0,
+        next: INIT_NEXT_VALUE as u64,
+        stop: 1 << IPV4_LEN,
+    };
     fn addr_check(ip_addr: &Ipv4Addr, prefix_len: u8) -> Result<(), SubnetworkErrors> {
         if prefix_len > IPV4_LEN {
-            let error_addr = format!("{}/{}", ip_addr, prefix_len);
+            let error_addr = format!("{}/{}, {}", ip_addr, prefix_len, IPV4_CIDR_HINT);
             Err(SubnetworkErrors::InvalidInputError {
                 msg: error_addr.to_string(),
             })
@@ -132,20 +422,54 @@ impl Ipv4Pool {
         }
     }
     fn addr_check_str(address: &str) -> Result<(Ipv4Addr, u8), SubnetworkErrors> {
-        if address.contains("/") {
-            let address_vec: Vec<&str> = address.split("/").collect();
+        // accept the url encoded slash, e.g. 192.168.1.0%2F24
+        let decoded = address.replacen("%2F", "/", 1).replacen("%2f", "/", 1);
+        if decoded.contains("/") {
+            let address_vec: Vec<&str> = decoded.split("/").collect();
             if address_vec.len() == 2 {
-                let ip_addr: Ipv4Addr = address_vec[0].parse()?;
-                let prefix_len: u8 = address_vec[1].parse()?;
-                if prefix_len <= IPV4_LEN {
-                    return Ok((ip_addr, prefix_len));
+                // parse errors fall through to the error with the format hint
+                let ip_addr = address_vec[0].parse::<Ipv4Addr>();
+                let prefix_len = address_vec[1].parse::<u8>();
+                if let (Ok(ip_addr), Ok(prefix_len)) = (ip_addr, prefix_len) {
+                    if prefix_len <= IPV4_LEN {
+                        return Ok((ip_addr, prefix_len));
+                    }
                 }
             }
         }
         Err(SubnetworkErrors::InvalidInputError {
-            msg: address.to_string(),
+            msg: format!("{}, {}", address, IPV4_CIDR_HINT),
         })
     }
+    fn build(addr: u32, prefix_len: u8) -> Ipv4Pool {
+        let mut mask: u32 = u32::MAX;
+        for _ in 0..(IPV4_LEN - prefix_len) {
+            mask <<= 1;
+        }
+        let exp = (IPV4_LEN - prefix_len) as u32;
+        let next = INIT_NEXT_VALUE as u64;
+        let stop = 1u64 << exp;
+        let prefix = addr & mask;
+        Ipv4Pool {
+            prefix,
+            mask,
+            next,
+            stop,
+        }
+    }
+    /// Returns the pools which together with `self` exactly cover its ancestor
+    /// network of length `ancestor_len`, in ascending order.
+    fn exclude_from_ancestor(&self, ancestor_len: u8) -> Vec<Ipv4Pool> {
+        let prefix_len = self.prefix_len();
+        let mut ret = Vec::new();
+        for level in (ancestor_len + 1)..=prefix_len {
+            let level_mask = u32::MAX << (IPV4_LEN - level);
+            let sibling = (self.prefix & level_mask) ^ (1 << (IPV4_LEN - level));
+            ret.push(Ipv4Pool::build(sibling, level));
+        }
+        ret.sort_by_key(|p| p.prefix);
+        ret
+    }
     /// Returns an Ipv4 iterator over the addresses contained in the network.
     ///
     /// # Example
@@ -163,23 +487,7 @@ impl Ipv4Pool {
     /// ```
     pub fn new(address: Ipv4Addr, prefix_len: u8) -> Result<Ipv4Pool, SubnetworkErrors> {
         match Ipv4Pool::addr_check(&address, prefix_len) {
-            Ok(_) => {
-                let addr: u32 = address.into();
-                let mut mask: u32 = u32::MAX;
-                for _ in 0..(IPV4_LEN - prefix_len) {
-                    mask <<= 1;
-                }
-                let exp = (IPV4_LEN - prefix_len) as u32;
-                let next = INIT_NEXT_VALUE as u32;
-                let stop = u32::pow(2, exp);
-                let prefix = addr & mask;
-                return Ok(Ipv4Pool {
-                    prefix,
-                    mask,
-                    next,
-                    stop,
-                });
-            }
+            Ok(_) => Ok(Ipv4Pool::build(address.into(), prefix_len)),
             Err(e) => Err(e),
         }
     }
@@ -198,24 +506,45 @@ impl Ipv4Pool {
     /// ```
     pub fn from(address: &str) -> Result<Ipv4Pool, SubnetworkErrors> {
         match Ipv4Pool::addr_check_str(address) {
-            Ok((ip_addr, prefix_len)) => {
-                let ip_addr: u32 = ip_addr.into();
-                let mut mask: u32 = u32::MAX;
-                for _ in 0..(IPV4_LEN - prefix_len) {
-                    mask <<= 1;
+            Ok((ip_addr, prefix_len)) => Ok(Ipv4Pool::build(ip_addr.into(), prefix_len)),
+            Err(e) => Err(e),
+        }
+    }
+    /// Returns the smallest `Ipv4Pool` starting at `network` with room for `hosts` usable hosts.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// fn main() {
+    ///     let network = Ipv4Addr::new(192, 168, 1, 0);
+    ///     let ips = Ipv4Pool::for_host_count(network, 200).unwrap();
+    ///     assert_eq!(ips.len(), 256);
+    /// }
+    /// ```
+    pub fn for_host_count(network: Ipv4Addr, hosts: u32) -> Result<Ipv4Pool, SubnetworkErrors> {
+        let prefix_len = (0..=IPV4_LEN).rev().find(|&prefix_len| {
+            let usable = match prefix_len {
+                31 => 2,
+                32 => 1,
+                _ => (1u64 << (IPV4_LEN - prefix_len)) - 2,
+            };
+            usable >= hosts as u64
+        });
+        match prefix_len {
+            Some(prefix_len) => {
+                let ips = Ipv4Pool::new(network, prefix_len)?;
+                if ips.network() != network {
+                    let msg = format!("{}/{}, network is not aligned", network, prefix_len);
+                    return Err(SubnetworkErrors::InvalidInputError { msg });
                 }
-                let exp = (IPV4_LEN - prefix_len) as u32;
-                let next = INIT_NEXT_VALUE as u32;
-                let stop = u32::pow(2, exp);
-                let prefix = ip_addr & mask;
-                return Ok(Ipv4Pool {
-                    prefix,
-                    mask,
-                    next,
-                    stop,
-                });
+                Ok(ips)
+            }
+            None => {
+                let msg = format!("{} hosts do not fit in any ipv4 network", hosts);
+                Err(SubnetworkErrors::InvalidInputError { msg })
             }
-            Err(e) => Err(e),
         }
     }
     /// Check if ip pool contains this ip.
@@ -266,6 +595,31 @@ impl Ipv4Pool {
             false
         }
     }
+    /// Returns where `address` is relative to this `Ipv4Pool`: `Less` below the network address,
+    /// `Greater` above the broadcast address and `Equal` when it is contained.
+    ///
+    /// # Example
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use std::net::Ipv4Addr;
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     let ret = ips.position_of(Ipv4Addr::new(192, 168, 0, 1));
+    ///     assert_eq!(ret, Ordering::Less);
+    /// }
+    /// ```
+    pub fn position_of(&self, address: Ipv4Addr) -> Ordering {
+        let addr: u32 = address.into();
+        if addr < self.prefix {
+            Ordering::Less
+        } else if addr > self.prefix | !self.mask {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
     /// Returns the address of the network denoted by this `Ipv4Pool`.
     /// This means the lowest possible IP address inside of the network.
     pub fn network(&self) -> Ipv4Addr {
@@ -278,687 +632,4572 @@ impl Ipv4Pool {
         let ret = self.prefix + biggest;
         ret.into()
     }
-    /// Returns the number of possible addresses in this `Ipv4Pool` (include 0 and 255)
-    pub fn size(&self) -> usize {
-        let biggest = !self.mask + 1;
-        biggest as usize
+    /// Returns the prefix length of this `Ipv4Pool`.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("10.0.0.0/12").unwrap();
+    ///     assert_eq!(ips.prefix_len(), 12);
+    /// }
+    /// ```
+    pub fn prefix_len(&self) -> u8 {
+        self.mask.count_ones() as u8
     }
-    /// Returns the number of valid addresses in this `Ipv4Pool` (NOT include 0 and 255)
-    pub fn len(&self) -> usize {
-        let length = !self.mask - 1;
-        length as usize
+    /// Returns the netmask of this `Ipv4Pool`, e.g. `255.255.255.0` for a `/24`.
+    ///
+    /// # Example
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     assert_eq!(ips.netmask(), Ipv4Addr::new(255, 255, 255, 0));
+    ///     assert_eq!(ips.hostmask(), Ipv4Addr::new(0, 0, 0, 255));
+    /// }
+    /// ```
+    pub fn netmask(&self) -> Ipv4Addr {
+        self.mask.into()
     }
-}
-
-#[derive(Debug, Clone, Copy)]
-pub struct CrossIpv6Pool {
-    start: u128,
-    end: u128,
-    next: u128,
-}
-
-impl Iterator for CrossIpv6Pool {
-    type Item = Ipv6Addr;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.next <= self.end {
-            let ret = self.next;
-            self.next += 1;
-            Some(ret.into())
-        } else {
-            None
+    /// Returns the hostmask (wildcard mask) of this `Ipv4Pool`, e.g. `0.0.0.255` for a `/24`.
+    pub fn hostmask(&self) -> Ipv4Addr {
+        (!self.mask).into()
+    }
+    /// Returns the addresses of this `Ipv4Pool` as an inclusive integer range,
+    /// from the network address to the broadcast address.
+    pub fn as_u32_range(&self) -> RangeInclusive<u32> {
+        self.prefix..=(self.prefix | !self.mask)
+    }
+    /// Returns this `Ipv4Pool` as a JSON object with the network, prefix, netmask and broadcast.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     println!("{}", ips.to_json());
+    /// }
+    /// ```
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"network\":\"{}\",\"prefix\":{},\"netmask\":\"{}\",\"broadcast\":\"{}\"}}",
+            self.network(),
+            self.prefix_len(),
+            self.netmask(),
+            self.broadcast()
+        )
+    }
+    /// Returns the destination of this `Ipv4Pool` for `ip route`, e.g. `192.168.1.0/24`.
+    pub fn to_ip_route_dst(&self) -> String {
+        format!("{}/{}", self.network(), self.prefix_len())
+    }
+    /// Returns the network and netmask of this `Ipv4Pool` in `ifconfig` form,
+    /// e.g. `192.168.1.0 netmask 255.255.255.0`.
+    pub fn to_ifconfig_form(&self) -> String {
+        format!("{} netmask {}", self.network(), self.netmask())
+    }
+    /// Returns the IPv4-mapped `Ipv6Pool` of this `Ipv4Pool`, e.g. `::ffff:192.168.1.0/120`
+    /// for `192.168.1.0/24`.
+    pub fn to_ipv4_mapped(&self) -> Ipv6Pool {
+        let prefix_len = self.prefix_len() + 96;
+        Ipv6Pool::build(0xffff_0000_0000 | self.prefix as u128, prefix_len)
+    }
+    /// Returns the network address of this `Ipv4Pool` in dotted binary form
+    /// with a `|` marking where the prefix ends.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/26").unwrap();
+    ///     let ret = ips.to_binary_with_boundary();
+    ///     assert_eq!(ret, "11000000.10101000.00000001.00|000000");
+    /// }
+    /// ```
+    pub fn to_binary_with_boundary(&self) -> String {
+        let prefix_len = self.mask.count_ones();
+        let mut ret = String::new();
+        for i in 0..IPV4_LEN as u32 {
+            if i > 0 && i % 8 == 0 {
+                ret.push('.');
+            }
+            if i == prefix_len {
+                ret.push('|');
+            }
+            let bit = (self.prefix >> (IPV4_LEN as u32 - 1 - i)) & 1;
+            ret.push(if bit == 1 { '1' } else { '0' });
+        }
+        if prefix_len == IPV4_LEN as u32 {
+            ret.push('|');
         }
+        ret
     }
-}
-
-impl fmt::Display for CrossIpv6Pool {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let start: Ipv6Addr = self.start.into();
-        let end: Ipv6Addr = self.end.into();
-        write!(f, "{}-{}", start, end)
+    /// Returns all addresses of this `Ipv4Pool`, from the network address to the broadcast address.
+    pub fn to_vec(&self) -> Vec<Ipv4Addr> {
+        (self.prefix..=(self.prefix | !self.mask))
+            .map(Ipv4Addr::from)
+            .collect()
     }
-}
-
-impl CrossIpv6Pool {
-    /// Returns an Ipv4 iterator over the cross different subnetwork addresses.
+    /// Returns all addresses of this `Ipv4Pool` joined by newlines.
+    ///
+    /// The whole text is held in memory (up to 16 bytes per address),
+    /// keep the pool small.
+    pub fn to_lines(&self) -> String {
+        let mut ret = String::new();
+        for (i, addr) in self.as_u32_range().map(Ipv4Addr::from).enumerate() {
+            if i > 0 {
+                ret.push('\n');
+            }
+            ret.push_str(&addr.to_string());
+        }
+        ret
+    }
+    /// Returns an iterator over the usable host addresses of this `Ipv4Pool`, which skips
+    /// the network and broadcast addresses. `/31` (RFC 3021) and `/32` pools yield all their addresses.
     ///
     /// # Example
     /// ```
-    /// use subnetwork::CrossIpv6Pool;
-    /// use std::net::Ipv6Addr;
+    /// use subnetwork::Ipv4Pool;
     ///
     /// fn main() {
-    ///     let start_str = "fe80::215:5dff:fe20:b393";
-    ///     let end_str = "fe80::215:5dff:fe20:b395";
-    ///     let start: Ipv6Addr = start_str.parse().unwrap();
-    ///     let end: Ipv6Addr = end_str.parse().unwrap();
-    ///     let ips = CrossIpv6Pool::new(start, end).unwrap();
-    ///     for i in ips {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     for i in ips.hosts() {
     ///         println!("{:?}", i);
     ///     }
     /// }
     /// ```
-    pub fn new(start: Ipv6Addr, end: Ipv6Addr) -> Result<CrossIpv6Pool, SubnetworkErrors> {
-        let start_ipv6 = Ipv6::new(start);
-        let end_ipv6 = Ipv6::new(end);
-        if start_ipv6.addr <= end_ipv6.addr {
-            let cip = CrossIpv6Pool {
-                start: start_ipv6.addr,
-                end: end_ipv6.addr,
-                next: start_ipv6.addr,
-            };
-            Ok(cip)
+    pub fn hosts(&self) -> impl Iterator<Item = Ipv4Addr> {
+        let broadcast = self.prefix | !self.mask;
+        let (start, end) = if self.mask.count_ones() >= 31 {
+            (self.prefix, broadcast)
         } else {
-            let msg = format!("{}-{}", start, end);
-            Err(SubnetworkErrors::InvalidInputError { msg })
-        }
+            (self.prefix + 1, broadcast - 1)
+        };
+        (start..=end).map(Ipv4Addr::from)
     }
-}
-
-#[derive(Debug, Clone, Copy)]
-pub struct Ipv6Pool {
-    prefix: u128,
-    mask: u128,
-    next: u128,
-    stop: u128,
-}
-
-impl Iterator for Ipv6Pool {
-    type Item = Ipv6Addr;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.next < self.stop {
-            let ret = self.prefix + self.next;
-            self.next += 1;
-            Some(ret.into())
-        } else {
-            None
-        }
-    }
-}
-
-impl fmt::Display for Ipv6Pool {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let prefix: Ipv6Addr = self.prefix.into();
-        let mut prefix_len = 0;
-        let mut mask = self.mask;
-        while mask != 0 {
-            mask <<= 1;
-            prefix_len += 1;
-        }
-        write!(f, "{}/{}", prefix, prefix_len)
+    /// Returns an iterator over consecutive address pairs of this `Ipv4Pool`, `(.0, .1)`,
+    /// `(.2, .3)` and so on, e.g. to build `/31` point-to-point links.
+    /// A `/32` pool has a single address which is dropped, so it yields nothing.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("10.0.0.0/29").unwrap();
+    ///     for (a, b) in ips.pairs() {
+    ///         println!("{} <-> {}", a, b);
+    ///     }
+    /// }
+    /// ```
+    pub fn pairs(&self) -> impl Iterator<Item = (Ipv4Addr, Ipv4Addr)> {
+        let start = self.prefix as u64;
+        let end = (self.prefix | !self.mask) as u64;
+        (start..end)
+            .step_by(2)
+            .map(|a| (Ipv4Addr::from(a as u32), Ipv4Addr::from(a as u32 + 1)))
     }
-}
-
-impl Ipv6Pool {
-    fn addr_check(ip_addr: &Ipv6Addr, prefix_len: u8) -> Result<(), SubnetworkErrors> {
-        if prefix_len > IPV6_LEN {
-            let error_addr = format!("{}/{}", ip_addr, prefix_len);
-            Err(SubnetworkErrors::InvalidInputError {
-                msg: error_addr.to_string(),
-            })
+    /// Returns an iterator over the addresses of this `Ipv4Pool` except the network address,
+    /// keeping the broadcast address. `/31` and `/32` pools yield all their addresses.
+    pub fn iter_without_network(&self) -> impl Iterator<Item = Ipv4Addr> {
+        let start = if self.mask.count_ones() >= 31 {
+            self.prefix
         } else {
-            Ok(())
-        }
+            self.prefix + 1
+        };
+        (start..=(self.prefix | !self.mask)).map(Ipv4Addr::from)
     }
-    fn addr_check_str(address: &str) -> Result<(Ipv6Addr, u8), SubnetworkErrors> {
-        if address.contains("/") {
-            let address_vec: Vec<&str> = address.split("/").collect();
-            if address_vec.len() == 2 {
-                let addr: Ipv6Addr = address_vec[0].parse()?;
-                let prefix_len: u8 = address_vec[1].parse()?;
-                if prefix_len <= IPV6_LEN {
-                    return Ok((addr, prefix_len));
-                }
-            }
-        }
-        Err(SubnetworkErrors::InvalidInputError {
-            msg: address.to_string(),
-        })
+    /// Returns an iterator over the addresses of this `Ipv4Pool` starting at `start_offset`
+    /// from the network address up to the broadcast address, yielding nothing when
+    /// `start_offset` is past the end of the pool.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     let first = ips.iter_from(100).next();
+    ///     assert_eq!(first, Some(Ipv4Addr::new(192, 168, 1, 100)));
+    /// }
+    /// ```
+    pub fn iter_from(&self, start_offset: u32) -> impl Iterator<Item = Ipv4Addr> {
+        let start = self.prefix as u64 + start_offset as u64;
+        let end = (self.prefix | !self.mask) as u64;
+        (start..=end).map(|addr| Ipv4Addr::from(addr as u32))
     }
-    /// Returns an Ipv6 iterator over the addresses contained in the network.
+    /// Returns an iterator over the addresses of this `Ipv4Pool` (from the network address
+    /// to the broadcast address) along with their reverse DNS (PTR) name.
     ///
     /// # Example
     /// ```
-    /// use subnetwork::Ipv6Pool;
-    /// use std::net::Ipv6Addr;
+    /// use subnetwork::Ipv4Pool;
     ///
     /// fn main() {
-    ///     let ipv6_str = "::ffff:192.10.2.0";
-    ///     let ipv6: Ipv6Addr = ipv6_str.parse().unwrap();
-    ///     let ips = Ipv6Pool::new(ipv6, 120).unwrap();
-    ///     for i in ips {
-    ///         println!("{:?}", i);
+    ///     let ips = Ipv4Pool::from("192.168.1.0/30").unwrap();
+    ///     for (ip, ptr) in ips.iter_with_ptr() {
+    ///         println!("{} {}", ip, ptr);
     ///     }
     /// }
     /// ```
-    pub fn new(address: Ipv6Addr, prefix_len: u8) -> Result<Ipv6Pool, SubnetworkErrors> {
-        match Ipv6Pool::addr_check(&address, prefix_len) {
-            Ok(_) => {
-                let addr: u128 = address.into();
-                let mut mask: u128 = u128::MAX;
-                for _ in 0..(IPV6_LEN - prefix_len) {
-                    mask <<= 1;
-                }
-                let exp = (IPV6_LEN - prefix_len) as u32;
-                let next = INIT_NEXT_VALUE as u128;
-                let stop = u128::pow(2, exp);
-                let prefix = addr & mask;
-                Ok(Ipv6Pool {
-                    prefix,
-                    mask,
-                    next,
-                    stop,
-                })
-            }
-            Err(e) => Err(e),
-        }
+    pub fn iter_with_ptr(&self) -> impl Iterator<Item = (Ipv4Addr, String)> {
+        (self.prefix..=(self.prefix | !self.mask))
+            .map(|addr| (Ipv4Addr::from(addr), Ipv4::from_u32(addr).reverse_pointer()))
     }
-    /// Returns an Ipv6 iterator over the addresses contained in the network.
+    /// Returns the `/24` reverse DNS zones covering this `Ipv4Pool`, one zone per `/24`
+    /// for short prefixes and the single containing zone for prefixes longer than `/24`.
     ///
     /// # Example
     /// ```
-    /// use subnetwork::Ipv6Pool;
+    /// use subnetwork::Ipv4Pool;
     ///
     /// fn main() {
-    ///     let ips = Ipv6Pool::from("::ffff:192.10.2.0/120").unwrap();
-    ///     for i in ips {
+    ///     let ips = Ipv4Pool::from("10.0.0.0/22").unwrap();
+    ///     let zones = ips.reverse_zones_24();
+    ///     assert_eq!(zones[0], "0.0.10.in-addr.arpa");
+    ///     assert_eq!(zones[3], "3.0.10.in-addr.arpa");
+    /// }
+    /// ```
+    pub fn reverse_zones_24(&self) -> Vec<String> {
+        let first = self.prefix >> 8;
+        let last = (self.prefix | !self.mask) >> 8;
+        (first..=last)
+            .map(|zone| {
+                let [_, a, b, c] = zone.to_be_bytes();
+                format!("{}.{}.{}.in-addr.arpa", c, b, a)
+            })
+            .collect()
+    }
+    /// Returns an iterator over the addresses of this `Ipv4Pool` in Gray code order,
+    /// so two consecutive addresses differ in exactly one bit.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/30").unwrap();
+    ///     for i in ips.iter_gray() {
     ///         println!("{:?}", i);
     ///     }
     /// }
     /// ```
-    pub fn from(address: &str) -> Result<Ipv6Pool, SubnetworkErrors> {
-        match Ipv6Pool::addr_check_str(address) {
-            Ok((addr, prefix_len)) => {
-                let addr: u128 = addr.into();
-                let mut mask: u128 = u128::MAX;
-                for _ in 0..(IPV6_LEN - prefix_len) {
-                    mask <<= 1;
-                }
-                let exp = (IPV6_LEN - prefix_len) as u32;
-                let next = INIT_NEXT_VALUE as u128;
-                let stop = u128::pow(2, exp);
-                let prefix = addr & mask;
-                Ok(Ipv6Pool {
-                    prefix,
-                    mask,
-                    next,
-                    stop,
-                })
-            }
-            Err(e) => Err(e),
-        }
+    pub fn iter_gray(&self) -> impl Iterator<Item = Ipv4Addr> {
+        let prefix = self.prefix;
+        (0..=!self.mask).map(move |i| Ipv4Addr::from(prefix + (i ^ (i >> 1))))
     }
-    /// Check if ip pool contains this ip.
+    /// Returns an iterator over the addresses of this `Ipv4Pool` (from the network address
+    /// to the broadcast address) grouped in chunks of `size`, the last one possibly smaller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn batched(&self, size: usize) -> impl Iterator<Item = Vec<Ipv4Addr>> {
+        let start = self.prefix as u64;
+        let end = (self.prefix | !self.mask) as u64 + 1;
+        (start..end).step_by(size).map(move |chunk| {
+            let chunk_end = end.min(chunk + size as u64);
+            let mut batch = Vec::with_capacity((chunk_end - chunk) as usize);
+            batch.extend((chunk..chunk_end).map(|addr| Ipv4Addr::from(addr as u32)));
+            batch
+        })
+    }
+    /// Returns an iterator over the addresses of this `Ipv4Pool` (from the network address
+    /// to the broadcast address) which are not in `skip`.
     ///
     /// # Example
     /// ```
-    /// use subnetwork::Ipv6Pool;
+    /// use std::net::Ipv4Addr;
+    /// use subnetwork::{CrossIpv4Pool, Ipv4Pool};
     ///
     /// fn main() {
-    ///     let ips = Ipv6Pool::from("::ffff:192.10.2.0/120").unwrap();
-    ///     let ret = ips.contain_from_str("::ffff:192.10.2.1").unwrap();
-    ///     assert_eq!(ret, true);
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     let start = Ipv4Addr::new(192, 168, 1, 100);
+    ///     let end = Ipv4Addr::new(192, 168, 1, 199);
+    ///     let skip = CrossIpv4Pool::new(start, end).unwrap();
+    ///     assert_eq!(ips.iter_excluding_range(&skip).count(), 156);
     /// }
     /// ```
-    pub fn contain_from_str(&self, address: &str) -> Result<bool, SubnetworkErrors> {
-        match Ipv6Addr::from_str(address) {
-            Ok(addr) => {
-                let addr: u128 = addr.into();
-                if addr & self.mask == self.prefix {
-                    Ok(true)
-                } else {
-                    Ok(false)
-                }
-            }
-            Err(e) => Err(e.into()),
+    pub fn iter_excluding_range(&self, skip: &CrossIpv4Pool) -> impl Iterator<Item = Ipv4Addr> {
+        let start = self.prefix as u64;
+        let end = (self.prefix | !self.mask) as u64 + 1;
+        // clamp the skipped range into the pool, then jump over it
+        let skip_start = (skip.start as u64).clamp(start, end);
+        let skip_end = (skip.end as u64 + 1).clamp(skip_start, end);
+        (start..skip_start)
+            .chain(skip_end..end)
+            .map(|addr| Ipv4Addr::from(addr as u32))
+    }
+    /// Returns the gateway address of this `Ipv4Pool`.
+    /// This uses the common low-address convention, the gateway is the network address + 1
+    /// (a `/32` pool returns its only address).
+    pub fn gateway(&self) -> Ipv4Addr {
+        if self.mask == u32::MAX {
+            self.prefix.into()
+        } else {
+            (self.prefix + 1).into()
         }
     }
-    /// Check if ip pool contains this ip.
+    /// Returns `true` if `address` is the gateway of this `Ipv4Pool` under `convention`.
+    /// The network and broadcast addresses are never a gateway.
     ///
     /// # Example
     /// ```
-    /// use std::net::Ipv6Addr;
-    /// use std::str::FromStr;
-    /// use subnetwork::Ipv6Pool;
+    /// use std::net::Ipv4Addr;
+    /// use subnetwork::{GatewayConvention, Ipv4Pool};
     ///
     /// fn main() {
-    ///     let ips = Ipv6Pool::from("::ffff:192.10.2.0/120").unwrap();
-    ///     let ip = Ipv6Addr::from_str("::ffff:192.10.2.1").unwrap();
-    ///     let ret = ips.contain(ip);
-    ///     assert_eq!(ret, true);
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     let ip = Ipv4Addr::new(192, 168, 1, 254);
+    ///     assert!(ips.is_gateway(ip, GatewayConvention::Last));
     /// }
     /// ```
-    pub fn contain(&self, address: Ipv6Addr) -> bool {
-        let addr: u128 = address.into();
-        if addr & self.mask == self.prefix {
-            true
-        } else {
-            false
+    pub fn is_gateway(&self, address: Ipv4Addr, convention: GatewayConvention) -> bool {
+        let addr: u32 = address.into();
+        let broadcast = self.prefix | !self.mask;
+        if addr == self.prefix || addr == broadcast {
+            return false;
+        }
+        match convention {
+            GatewayConvention::First => addr == self.prefix + 1,
+            GatewayConvention::Last => addr == broadcast - 1,
         }
     }
-    /// Returns the address of the network denoted by this `Ipv6Pool`.
-    /// This means the lowest possible IP address inside of the network.
-    pub fn network(&self) -> Ipv6Addr {
-        self.prefix.into()
+    /// Returns the network address and the gateway address of this `Ipv4Pool` together.
+    ///
+    /// # Example
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     let (network, gateway) = ips.network_and_gateway();
+    ///     assert_eq!(network, Ipv4Addr::new(192, 168, 1, 0));
+    ///     assert_eq!(gateway, Ipv4Addr::new(192, 168, 1, 1));
+    /// }
+    /// ```
+    pub fn network_and_gateway(&self) -> (Ipv4Addr, Ipv4Addr) {
+        (self.network(), self.gateway())
     }
-    /// Returns the number of possible host addresses in this `Ipv6Pool` (include 0 and 255)
+    /// Returns the number of possible addresses in this `Ipv4Pool` (include 0 and 255),
+    /// the same as `len()`.
     pub fn size(&self) -> usize {
-        let biggest = !self.mask + 1;
-        biggest as usize
+        self.len()
     }
-    /// Returns the number of valid addresses in this `Ipv6Pool` (NOT include 0 and 255)
+    /// Returns the number of addresses in this `Ipv4Pool` (include 0 and 255),
+    /// which is exactly the length of the `Vec` returned by `to_vec()`.
+    /// Use `usable_hosts()` for the count without the network and broadcast addresses,
+    /// which is what `len()` returned before 0.5.0.
+    /// Saturates at `usize::MAX` for a `/0` on 32-bit targets.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     assert_eq!(ips.len(), 256);
+    ///     assert_eq!(ips.usable_hosts(), 254);
+    /// }
+    /// ```
     pub fn len(&self) -> usize {
-        let length = !self.mask - 1;
-        length as usize
+        usize::try_from(!self.mask as u64 + 1).unwrap_or(usize::MAX)
     }
-}
-
-/* Single Addr Struct */
-
-#[derive(Debug, Clone, Copy)]
-pub struct Ipv4 {
-    addr: u32,
-}
-
-impl fmt::Display for Ipv4 {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let addr: Ipv4Addr = self.addr.into();
-        write!(f, "{}", addr)
+    /// Returns the exact length of the `Vec` returned by `to_vec()`, to preallocate
+    /// buffers in calling code. This is the same as `len()`.
+    pub fn max_vec_len(&self) -> usize {
+        self.len()
     }
-}
-
-impl Ipv4 {
-    fn prefix_len_check(&self, prefix_len: u8) -> Result<(), SubnetworkErrors> {
-        if prefix_len > IPV4_LEN {
-            let addr: Ipv4Addr = self.addr.into();
-            let error_msg = format!("{}/{}", addr, prefix_len);
-            Err(SubnetworkErrors::InvalidInputError { msg: error_msg })
-        } else {
-            Ok(())
-        }
+    /// Returns `true` if this `Ipv4Pool` is a `/32`, holding a single address.
+    pub fn is_single_host(&self) -> bool {
+        self.mask == u32::MAX
     }
-    /// Constructs a new `Ipv4` from a given Ipv4Addr.
-    pub fn new(address: Ipv4Addr) -> Ipv4 {
-        // address: 192.168.1.1
-        let addr: u32 = address.into();
-        Ipv4 { addr }
+    /// Returns `true` if the prefix length of this `Ipv4Pool` falls on an octet boundary
+    /// (`/0`, `/8`, `/16`, `/24` or `/32`).
+    pub fn is_octet_aligned(&self) -> bool {
+        self.mask.count_ones().is_multiple_of(8)
     }
-    /// Constructs a new `Ipv4` from a given `&str`.
+    /// Returns `true` if this `Ipv4Pool` can be assigned as a regular subnet, that is its
+    /// prefix is at most `/30` so it has a distinct network, broadcast and at least 2 usable hosts.
+    pub fn is_assignable_subnet(&self) -> bool {
+        self.mask.count_ones() <= 30
+    }
+    /// Returns `true` if this whole `Ipv4Pool` is within one of the private blocks of RFC 1918,
+    /// `10.0.0.0/8`, `172.16.0.0/12` or `192.168.0.0/16`.
     ///
     /// # Example
     /// ```
-    /// use subnetwork::Ipv4;
+    /// use subnetwork::Ipv4Pool;
     ///
     /// fn main() {
-    ///     let ipv4 = Ipv4::from("192.168.1.1").unwrap();
-    ///     for i in ipv4.iter(24).unwrap() {
-    ///         println!("{:?}", i);
-    ///     }
+    ///     let ips = Ipv4Pool::from("172.16.8.0/22").unwrap();
+    ///     assert!(ips.is_private());
+    ///     let ips = Ipv4Pool::from("172.0.0.0/8").unwrap();
+    ///     assert!(!ips.is_private());
     /// }
     /// ```
-    pub fn from(address: &str) -> Result<Ipv4, SubnetworkErrors> {
-        // address: 192.168.1.1
-        match Ipv4Addr::from_str(address) {
-            Ok(addr) => {
-                let addr: u32 = addr.into();
-                Ok(Ipv4 { addr })
-            }
-            Err(e) => Err(e.into()),
-        }
+    pub fn is_private(&self) -> bool {
+        let private = [
+            Ipv4Pool::build(Ipv4Addr::new(10, 0, 0, 0).into(), 8),
+            Ipv4Pool::build(Ipv4Addr::new(172, 16, 0, 0).into(), 12),
+            Ipv4Pool::build(Ipv4Addr::new(192, 168, 0, 0).into(), 16),
+        ];
+        private.iter().any(|p| p.contains_pool(self))
     }
-    pub fn iter(&self, prefix_len: u8) -> Result<Ipv4Pool, SubnetworkErrors> {
-        match self.prefix_len_check(prefix_len) {
-            Ok(_) => {
-                let mut mask: u32 = u32::MAX;
-                for _ in 0..(IPV4_LEN - prefix_len) {
-                    mask <<= 1;
-                }
-                let exp = (IPV4_LEN - prefix_len) as u32;
-                let next = INIT_NEXT_VALUE as u32;
-                let stop = u32::pow(2, exp);
-                let prefix = self.addr & mask;
-                Ok(Ipv4Pool {
-                    prefix,
-                    mask,
-                    next,
-                    stop,
-                })
-            }
-            Err(e) => Err(e),
+    /// Returns the number of usable host addresses in this `Ipv4Pool`, which excludes
+    /// the network and broadcast addresses except for `/31` (RFC 3021) and `/32` pools.
+    pub fn usable_hosts(&self) -> u64 {
+        match self.mask.count_ones() {
+            31 => 2,
+            32 => 1,
+            _ => !self.mask as u64 - 1,
         }
     }
-    /// Returns the standard IPv4 address.
-    pub fn to_std(&self) -> Ipv4Addr {
-        self.addr.into()
+    /// Returns how many addresses this `Ipv4Pool` has yielded as an iterator so far,
+    /// from either end.
+    pub fn addresses_consumed(&self) -> u64 {
+        self.address_count() - self.addresses_remaining()
     }
-    /// Returns the largest identical prefix of two IP addresses.
+    /// Returns how many addresses this `Ipv4Pool` will still yield as an iterator.
+    ///
     /// # Example
     /// ```
-    /// use subnetwork::{Ipv4, Ipv4Pool};
+    /// use subnetwork::Ipv4Pool;
     ///
     /// fn main() {
-    ///     let ipv4_1 = Ipv4::from("192.168.1.136").unwrap();
-    ///     let ipv4_2 = Ipv4::from("192.168.1.192").unwrap();
-    ///     let ret = ipv4_1.largest_identical_prefix(ipv4_2);
-    ///     assert_eq!(ret, 25);
+    ///     let mut ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     ips.next();
+    ///     assert_eq!(ips.addresses_consumed(), 1);
+    ///     assert_eq!(ips.addresses_remaining(), 255);
     /// }
     /// ```
-    pub fn largest_identical_prefix(&self, target: Ipv4) -> u32 {
-        let a = self.addr;
-        let b = target.addr;
-        let mut mask = 1;
-        for _ in 0..(IPV4_LEN - 1) {
-            mask <<= 1;
-        }
-        let mut count = 0;
-        for _ in 0..IPV4_LEN {
-            if a & mask != b & mask {
-                break;
-            }
-            count += 1;
-            mask >>= 1;
+    pub fn addresses_remaining(&self) -> u64 {
+        self.stop - self.next
+    }
+    /// Returns the offset of `address` from the network address of this `Ipv4Pool`,
+    /// or `None` if the address is not in the pool.
+    ///
+    /// # Example
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     let offset = ips.offset_of(Ipv4Addr::new(192, 168, 1, 20));
+    ///     assert_eq!(offset, Some(20));
+    /// }
+    /// ```
+    pub fn offset_of(&self, address: Ipv4Addr) -> Option<u32> {
+        if self.contain(address) {
+            let addr: u32 = address.into();
+            Some(addr - self.prefix)
+        } else {
+            None
         }
-        count
     }
-}
-
-#[derive(Debug, Clone, Copy)]
-pub struct Ipv6 {
-    addr: u128,
-}
-
-impl fmt::Display for Ipv6 {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let addr: Ipv6Addr = self.addr.into();
-        write!(f, "{}", addr)
+    /// Returns the addresses right before and right after `address` in this `Ipv4Pool`,
+    /// `None` past the edges of the pool or if `address` is not in the pool.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     let (prev, next) = ips.neighbors(Ipv4Addr::new(192, 168, 1, 10));
+    ///     assert_eq!(prev, Some(Ipv4Addr::new(192, 168, 1, 9)));
+    ///     assert_eq!(next, Some(Ipv4Addr::new(192, 168, 1, 11)));
+    /// }
+    /// ```
+    pub fn neighbors(&self, address: Ipv4Addr) -> (Option<Ipv4Addr>, Option<Ipv4Addr>) {
+        if !self.contain(address) {
+            return (None, None);
+        }
+        let addr: u32 = address.into();
+        let last = self.prefix | !self.mask;
+        let prev = if addr > self.prefix {
+            Some((addr - 1).into())
+        } else {
+            None
+        };
+        let next = if addr < last {
+            Some((addr + 1).into())
+        } else {
+            None
+        };
+        (prev, next)
     }
-}
-
-impl Ipv6 {
-    fn prefix_len_check(&self, prefix_len: u8) -> Result<(), SubnetworkErrors> {
-        if prefix_len > IPV6_LEN {
-            let addr: Ipv6Addr = self.addr.into();
-            let msg = format!("{}/{}", addr, prefix_len);
-            Err(SubnetworkErrors::InvalidInputError { msg })
+    /// Returns `true` if every address of `other` is contained in this `Ipv4Pool`.
+    pub fn contains_pool(&self, other: &Ipv4Pool) -> bool {
+        self.mask.count_ones() <= other.mask.count_ones() && other.prefix & self.mask == self.prefix
+    }
+    /// Returns `true` if the network address of this `Ipv4Pool` is a usable host of `parent`,
+    /// i.e. neither the network nor the broadcast address of a `parent` up to `/30`.
+    pub fn network_is_host_of(&self, parent: &Ipv4Pool) -> bool {
+        if !parent.contain(self.network()) {
+            false
+        } else if parent.mask.count_ones() >= 31 {
+            true
         } else {
-            Ok(())
+            self.network() != parent.network() && self.network() != parent.broadcast()
         }
     }
-    /// Constructs a new `Ipv6` from a given Ipv6Addr.
-    pub fn new(address: Ipv6Addr) -> Ipv6 {
-        let addr: u128 = address.into();
-        Ipv6 { addr }
+    /// Returns `true` if every address of the `range` is contained in this `Ipv4Pool`.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::{CrossIpv4Pool, Ipv4Pool};
+    /// use std::net::Ipv4Addr;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.0.0/16").unwrap();
+    ///     let start = Ipv4Addr::new(192, 168, 1, 1);
+    ///     let end = Ipv4Addr::new(192, 168, 3, 254);
+    ///     let range = CrossIpv4Pool::new(start, end).unwrap();
+    ///     assert!(ips.contains_range(&range));
+    /// }
+    /// ```
+    pub fn contains_range(&self, range: &CrossIpv4Pool) -> bool {
+        self.contain(range.start.into()) && self.contain(range.end.into())
     }
-    /// Constructs a new `Ipv6` from a given `&str`.
+    /// Returns the parent network of this `Ipv4Pool`, one prefix bit shorter,
+    /// or `None` for `/0`.
     ///
     /// # Example
     /// ```
-    /// use subnetwork::Ipv6;
+    /// use std::net::Ipv4Addr;
+    /// use subnetwork::Ipv4Pool;
     ///
     /// fn main() {
-    ///     let ipv6 = Ipv6::from("::ffff:192.10.2.255").unwrap();
-    ///     for i in ipv6.iter(124) {
-    ///         println!("{:?}", i);
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     let parent = ips.supernet().unwrap();
+    ///     assert_eq!(parent.network(), Ipv4Addr::new(192, 168, 0, 0));
+    /// }
+    /// ```
+    pub fn supernet(&self) -> Option<Ipv4Pool> {
+        match self.mask.count_ones() {
+            0 => None,
+            prefix_len => Some(Ipv4Pool::build(self.prefix, prefix_len as u8 - 1)),
+        }
+    }
+    /// Returns `true` if this `Ipv4Pool` fully contains `other`, the same as [`Ipv4Pool::contains_pool`].
+    pub fn supernet_of(&self, other: &Ipv4Pool) -> bool {
+        self.contains_pool(other)
+    }
+    /// Returns `true` if this `Ipv4Pool` and `other` share at least one address.
+    pub fn overlaps(&self, other: &Ipv4Pool) -> bool {
+        self.contains_pool(other) || other.contains_pool(self)
+    }
+    /// Returns `true` if this `Ipv4Pool` and `other` have no address in common.
+    pub fn is_disjoint(&self, other: &Ipv4Pool) -> bool {
+        !self.overlaps(other)
+    }
+    /// Returns the addresses shared by this `Ipv4Pool` and `other`, which is always the more
+    /// specific of the two pools, or `None` when they are disjoint.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let a = Ipv4Pool::from("192.168.0.0/16").unwrap();
+    ///     let b = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     assert_eq!(a.intersection(&b), Some(b));
+    /// }
+    /// ```
+    pub fn intersection(&self, other: &Ipv4Pool) -> Option<Ipv4Pool> {
+        if self.contains_pool(other) {
+            Some(Ipv4Pool::build(other.prefix, other.prefix_len()))
+        } else if other.contains_pool(self) {
+            Some(Ipv4Pool::build(self.prefix, self.prefix_len()))
+        } else {
+            None
+        }
+    }
+    /// Returns the addresses of this `Ipv4Pool` and `other` as one sorted list,
+    /// addresses shared by both pools appear once.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let a = Ipv4Pool::from("192.168.1.0/25").unwrap();
+    ///     let b = Ipv4Pool::from("192.168.1.128/25").unwrap();
+    ///     let ret = a.union_addresses(&b);
+    ///     assert_eq!(ret.len(), 256);
+    /// }
+    /// ```
+    pub fn union_addresses(&self, other: &Ipv4Pool) -> Vec<Ipv4Addr> {
+        if self.contains_pool(other) {
+            self.to_vec()
+        } else if other.contains_pool(self) {
+            other.to_vec()
+        } else if self.prefix < other.prefix {
+            let mut ret = self.to_vec();
+            ret.extend(other.to_vec());
+            ret
+        } else {
+            let mut ret = other.to_vec();
+            ret.extend(self.to_vec());
+            ret
+        }
+    }
+    /// Returns the minimal set of pools covering the addresses of this `Ipv4Pool`
+    /// which are not in `other`, in ascending order.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.0.0/23").unwrap();
+    ///     let other = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     for i in ips.exclude(&other) {
+    ///         println!("{}", i);
     ///     }
     /// }
     /// ```
-    pub fn from(address: &str) -> Result<Ipv6, SubnetworkErrors> {
-        match Ipv6Addr::from_str(address) {
-            Ok(addr) => {
-                let addr: u128 = addr.into();
-                Ok(Ipv6 { addr })
+    pub fn exclude(&self, other: &Ipv4Pool) -> Vec<Ipv4Pool> {
+        if other.contains_pool(self) {
+            Vec::new()
+        } else if self.contains_pool(other) {
+            other.exclude_from_ancestor(self.prefix_len())
+        } else {
+            let prefix_len = self.prefix_len();
+            vec![Ipv4Pool::build(self.prefix, prefix_len)]
+        }
+    }
+    /// Returns the pools `(added, removed)` going from this `Ipv4Pool` to `other`,
+    /// `added` covers the addresses only in `other` and `removed` the addresses only in `self`.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let old = Ipv4Pool::from("192.168.0.0/23").unwrap();
+    ///     let new = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     let (added, removed) = old.diff(&new);
+    ///     assert!(added.is_empty());
+    ///     assert_eq!(removed.len(), 1);
+    /// }
+    /// ```
+    pub fn diff(&self, other: &Ipv4Pool) -> (Vec<Ipv4Pool>, Vec<Ipv4Pool>) {
+        (other.exclude(self), self.exclude(other))
+    }
+    /// Returns the minimal set of pools covering the addresses of this `Ipv4Pool`
+    /// which are in none of `others`, in ascending order.
+    pub fn exclude_all(&self, others: &[Ipv4Pool]) -> Vec<Ipv4Pool> {
+        let prefix_len = self.prefix_len();
+        let mut ret = vec![Ipv4Pool::build(self.prefix, prefix_len)];
+        for other in others {
+            ret = ret.iter().flat_map(|p| p.exclude(other)).collect();
+        }
+        ret.sort_by_key(|p| p.prefix);
+        ret
+    }
+    /// Returns the largest pool of this `Ipv4Pool` which is not covered by `allocated`,
+    /// or `None` if everything is allocated. Ties go to the lowest address.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("10.0.0.0/16").unwrap();
+    ///     let allocated = [Ipv4Pool::from("10.0.0.0/17").unwrap()];
+    ///     let free = ips.largest_free_block(&allocated).unwrap();
+    ///     println!("{}", free);
+    /// }
+    /// ```
+    pub fn largest_free_block(&self, allocated: &[Ipv4Pool]) -> Option<Ipv4Pool> {
+        self.exclude_all(allocated)
+            .into_iter()
+            .min_by_key(|p| (p.mask, p.prefix))
+    }
+    /// Returns the fraction of the addresses of this `Ipv4Pool` covered by `allocated`,
+    /// between 0 and 1. Overlapping allocations are only counted once.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("10.0.0.0/22").unwrap();
+    ///     let allocated = [Ipv4Pool::from("10.0.0.0/23").unwrap()];
+    ///     assert_eq!(ips.utilization(&allocated), 0.5);
+    /// }
+    /// ```
+    pub fn utilization(&self, allocated: &[Ipv4Pool]) -> f64 {
+        let total = self.address_count();
+        let free: u64 = self
+            .exclude_all(allocated)
+            .iter()
+            .map(|p| p.address_count())
+            .sum();
+        (total - free) as f64 / total as f64
+    }
+    /// Returns the minimal set of pools covering the whole IPv4 address space
+    /// except this `Ipv4Pool`, in ascending order.
+    pub fn complement(&self) -> Vec<Ipv4Pool> {
+        Ipv4Pool::FULL.exclude(self)
+    }
+    /// Returns the pool of length `prefix_len` holding the network address of this `Ipv4Pool`,
+    /// a supernet for a shorter prefix and the first contained subnet for a longer prefix.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     let supernet = ips.resize(16).unwrap();
+    ///     println!("{}", supernet);
+    /// }
+    /// ```
+    pub fn resize(&self, prefix_len: u8) -> Result<Ipv4Pool, SubnetworkErrors> {
+        Ipv4Pool::new(self.prefix.into(), prefix_len)
+    }
+    /// Returns the first `count` subnets of length `prefix_len` from the start of this `Ipv4Pool`.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     for i in ips.allocate_sequential(26, 3).unwrap() {
+    ///         println!("{}", i);
+    ///     }
+    /// }
+    /// ```
+    pub fn allocate_sequential(
+        &self,
+        prefix_len: u8,
+        count: usize,
+    ) -> Result<Vec<Ipv4Pool>, SubnetworkErrors> {
+        let self_prefix_len = self.prefix_len();
+        let network: Ipv4Addr = self.prefix.into();
+        if prefix_len < self_prefix_len || prefix_len > IPV4_LEN {
+            let msg = format!(
+                "{}/{}, expected prefix between {} and {}",
+                network, prefix_len, self_prefix_len, IPV4_LEN
+            );
+            return Err(SubnetworkErrors::InvalidInputError { msg });
+        }
+        let capacity = 1u64 << (prefix_len - self_prefix_len);
+        if count as u64 > capacity {
+            let msg = format!(
+                "{}/{} holds {} subnets of /{}, {} requested",
+                network, self_prefix_len, capacity, prefix_len, count
+            );
+            return Err(SubnetworkErrors::InvalidInputError { msg });
+        }
+        let step = 1u64 << (IPV4_LEN - prefix_len);
+        let ret = (0..count as u64)
+            .map(|i| Ipv4Pool::build(self.prefix + (i * step) as u32, prefix_len))
+            .collect();
+        Ok(ret)
+    }
+    /// Returns an iterator over all subnets of this `Ipv4Pool` for every prefix length
+    /// from `from_prefix` to `to_prefix`, level by level and in ascending order within a level.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     for i in ips.subnet_tree(25, 26).unwrap() {
+    ///         println!("{}", i);
+    ///     }
+    /// }
+    /// ```
+    pub fn subnet_tree(
+        &self,
+        from_prefix: u8,
+        to_prefix: u8,
+    ) -> Result<impl Iterator<Item = Ipv4Pool>, SubnetworkErrors> {
+        let self_prefix_len = self.prefix_len();
+        if from_prefix <= self_prefix_len || from_prefix > to_prefix || to_prefix > IPV4_LEN {
+            let network: Ipv4Addr = self.prefix.into();
+            let msg = format!(
+                "{}/{}, expected {} < from prefix {} <= to prefix {} <= {}",
+                network, self_prefix_len, self_prefix_len, from_prefix, to_prefix, IPV4_LEN
+            );
+            return Err(SubnetworkErrors::InvalidInputError { msg });
+        }
+        let prefix = self.prefix;
+        Ok((from_prefix..=to_prefix).flat_map(move |prefix_len| {
+            let count = 1u64 << (prefix_len - self_prefix_len);
+            let step = 1u64 << (IPV4_LEN - prefix_len);
+            (0..count).map(move |i| Ipv4Pool::build(prefix + (i * step) as u32, prefix_len))
+        }))
+    }
+    /// Returns an iterator over the subnets of length `new_prefix` contained in this `Ipv4Pool`,
+    /// in ascending order.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     for i in ips.subnets(26).unwrap() {
+    ///         println!("{}", i);
+    ///     }
+    /// }
+    /// ```
+    pub fn subnets(
+        &self,
+        new_prefix: u8,
+    ) -> Result<impl Iterator<Item = Ipv4Pool>, SubnetworkErrors> {
+        let self_prefix_len = self.prefix_len();
+        if new_prefix < self_prefix_len || new_prefix > IPV4_LEN {
+            let network: Ipv4Addr = self.prefix.into();
+            let msg = format!(
+                "{}/{}, expected prefix between {} and {}",
+                network, new_prefix, self_prefix_len, IPV4_LEN
+            );
+            return Err(SubnetworkErrors::InvalidInputError { msg });
+        }
+        let count = 1u64 << (new_prefix - self_prefix_len);
+        let step = 1u64 << (IPV4_LEN - new_prefix);
+        let prefix = self.prefix;
+        Ok((0..count).map(move |i| Ipv4Pool::build(prefix + (i * step) as u32, new_prefix)))
+    }
+    /// Returns every subnet of length `row_prefix` of this `Ipv4Pool` paired with its addresses,
+    /// e.g. one row per `/28` for a grid view.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     for (row, addrs) in ips.rows_by_prefix(28).unwrap() {
+    ///         println!("{}: {:?}", row, addrs);
+    ///     }
+    /// }
+    /// ```
+    pub fn rows_by_prefix(
+        &self,
+        row_prefix: u8,
+    ) -> Result<Vec<(Ipv4Pool, Vec<Ipv4Addr>)>, SubnetworkErrors> {
+        let rows = self.subnets(row_prefix)?;
+        Ok(rows.map(|row| (row, row.to_vec())).collect())
+    }
+    /// Returns the shortest prefix length splitting this `Ipv4Pool` into at least `n` subnets.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("10.0.0.0/16").unwrap();
+    ///     assert_eq!(ips.prefix_for_subnet_count(10).unwrap(), 20);
+    /// }
+    /// ```
+    pub fn prefix_for_subnet_count(&self, n: usize) -> Result<u8, SubnetworkErrors> {
+        let self_prefix_len = self.mask.count_ones();
+        let bits = (n as u64).next_power_of_two().trailing_zeros();
+        let prefix_len = self_prefix_len + bits;
+        if prefix_len > IPV4_LEN as u32 {
+            let network: Ipv4Addr = self.prefix.into();
+            let msg = format!(
+                "{}/{} can not be split into {} subnets",
+                network, self_prefix_len, n
+            );
+            return Err(SubnetworkErrors::InvalidInputError { msg });
+        }
+        Ok(prefix_len as u8)
+    }
+    /// Allocates one subnet per host requirement with variable length subnet masking (VLSM).
+    /// The requirements are served from the largest to the smallest, each subnet is the
+    /// smallest one whose addresses minus the network and broadcast addresses fit the hosts,
+    /// and the subnets are returned in that allocation order.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.0.0/16").unwrap();
+    ///     for i in ips.vlsm(&[100, 50, 20]).unwrap() {
+    ///         println!("{}", i);
+    ///     }
+    /// }
+    /// ```
+    pub fn vlsm(&self, host_requirements: &[u32]) -> Result<Vec<Ipv4Pool>, SubnetworkErrors> {
+        let mut requirements = host_requirements.to_vec();
+        requirements.sort_by(|a, b| b.cmp(a));
+        let end = (self.prefix | !self.mask) as u64 + 1;
+        let mut cursor = self.prefix as u64;
+        let mut ret = Vec::new();
+        for hosts in requirements {
+            let needed = hosts as u64 + 2;
+            let size = needed.next_power_of_two();
+            if cursor + size > end {
+                let network: Ipv4Addr = self.prefix.into();
+                let msg = format!(
+                    "{}/{} can not hold a subnet for {} hosts",
+                    network,
+                    self.mask.count_ones(),
+                    hosts
+                );
+                return Err(SubnetworkErrors::InvalidInputError { msg });
             }
-            Err(e) => Err(e.into()),
+            let prefix_len = IPV4_LEN - size.trailing_zeros() as u8;
+            ret.push(Ipv4Pool::build(cursor as u32, prefix_len));
+            cursor += size;
         }
+        Ok(ret)
     }
-    /// Returns an Ipv6 iterator over the addresses contained in the network.
-    pub fn iter(&self, prefix_len: u8) -> Result<Ipv6Pool, SubnetworkErrors> {
-        match self.prefix_len_check(prefix_len) {
-            Ok(_) => {
-                let mut mask: u128 = u128::MAX;
-                for _ in 0..(IPV6_LEN - prefix_len) {
-                    mask <<= 1;
-                }
-                let exp = (IPV6_LEN - prefix_len) as u32;
-                let next = INIT_NEXT_VALUE as u128;
-                let stop = u128::pow(2, exp);
-                let prefix = self.addr & mask;
-                Ok(Ipv6Pool {
-                    prefix,
-                    mask,
-                    next,
-                    stop,
-                })
+    /// Returns `true` if `subnets` tile this `Ipv4Pool` exactly, without gaps or overlaps.
+    pub fn is_fully_covered_by(&self, subnets: &[Ipv4Pool]) -> bool {
+        let mut ranges: Vec<(u64, u64)> = subnets
+            .iter()
+            .map(|p| (p.prefix as u64, (p.prefix | !p.mask) as u64))
+            .collect();
+        ranges.sort();
+        // the next address that must be covered
+        let mut expected = self.prefix as u64;
+        for (start, end) in ranges {
+            if start != expected {
+                return false;
             }
-            Err(e) => Err(e),
+            expected = end + 1;
         }
+        expected == (self.prefix | !self.mask) as u64 + 1
     }
-    /// Returns the node local scope multicast address of this `Ipv6`.
-    pub fn node_multicast(&self) -> Ipv6Addr {
-        let node = Ipv6Addr::new(
-            0xFF01, 0x0000, 0x0000, 0x0000, 0x0000, 0x0001, 0xFF00, 0x0000,
+    /// Returns the number of leading bits needed to tell the network of this `Ipv4Pool`
+    /// from the network of `other`, or the longer prefix length if both networks are identical.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let a = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     let b = Ipv4Pool::from("192.168.2.0/24").unwrap();
+    ///     assert_eq!(a.distinguishing_bits(&b), 23);
+    /// }
+    /// ```
+    pub fn distinguishing_bits(&self, other: &Ipv4Pool) -> u8 {
+        if self.prefix == other.prefix {
+            self.mask.count_ones().max(other.mask.count_ones()) as u8
+        } else {
+            let a = Ipv4::from_u32(self.prefix);
+            let b = Ipv4::from_u32(other.prefix);
+            (a.largest_identical_prefix(b) as u8 + 1).min(IPV4_LEN)
+        }
+    }
+    /// Returns the shortest prefix length whose subnet starting at the network address
+    /// of this `Ipv4Pool` does not contain `address`, or `None` if `address` is the network address.
+    ///
+    /// # Example
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     let ret = ips.shortest_prefix_excluding(Ipv4Addr::new(192, 168, 1, 128));
+    ///     assert_eq!(ret, Some(25));
+    /// }
+    /// ```
+    pub fn shortest_prefix_excluding(&self, address: Ipv4Addr) -> Option<u8> {
+        let network = Ipv4::from_u32(self.prefix);
+        let target = Ipv4::new(address);
+        if network.addr == target.addr {
+            None
+        } else {
+            Some(network.largest_identical_prefix(target) as u8 + 1)
+        }
+    }
+    /// Returns `true` if the prefix of this `Ipv4Pool` is longer than the prefix of `other`.
+    pub fn is_more_specific_than(&self, other: &Ipv4Pool) -> bool {
+        self.mask.count_ones() > other.mask.count_ones()
+    }
+    /// Returns `true` if the prefix of this `Ipv4Pool` is shorter than the prefix of `other`.
+    pub fn is_less_specific_than(&self, other: &Ipv4Pool) -> bool {
+        self.mask.count_ones() < other.mask.count_ones()
+    }
+}
+
+impl CoversAddresses for Ipv4Pool {
+    fn covers(&self, address: Ipv4Addr) -> bool {
+        self.contain(address)
+    }
+    fn address_count(&self) -> u64 {
+        !self.mask as u64 + 1
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct CrossIpv6Pool {
+    start: u128,
+    end: u128,
+    next: u128,
+}
+
+impl Iterator for CrossIpv6Pool {
+    type Item = Ipv6Addr;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next <= self.end {
+            let ret = self.next;
+            self.next += 1;
+            Some(ret.into())
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for CrossIpv6Pool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let start: Ipv6Addr = self.start.into();
+        let end: Ipv6Addr = self.end.into();
+        write!(f, "{}-{}", start, end)
+    }
+}
+
+impl CrossIpv6Pool {
+    /// Returns an Ipv4 iterator over the cross different subnetwork addresses.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::CrossIpv6Pool;
+    /// use std::net::Ipv6Addr;
+    ///
+    /// fn main() {
+    ///     let start_str = "fe80::215:5dff:fe20:b393";
+    ///     let end_str = "fe80::215:5dff:fe20:b395";
+    ///     let start: Ipv6Addr = start_str.parse().unwrap();
+    ///     let end: Ipv6Addr = end_str.parse().unwrap();
+    ///     let ips = CrossIpv6Pool::new(start, end).unwrap();
+    ///     for i in ips {
+    ///         println!("{:?}", i);
+    ///     }
+    /// }
+    /// ```
+    pub fn new(start: Ipv6Addr, end: Ipv6Addr) -> Result<CrossIpv6Pool, SubnetworkErrors> {
+        let start_ipv6 = Ipv6::new(start);
+        let end_ipv6 = Ipv6::new(end);
+        if start_ipv6.addr <= end_ipv6.addr {
+            let cip = CrossIpv6Pool {
+                start: start_ipv6.addr,
+                end: end_ipv6.addr,
+                next: start_ipv6.addr,
+            };
+            Ok(cip)
+        } else {
+            let msg = format!("{}-{}, {}", start, end, RANGE_HINT);
+            Err(SubnetworkErrors::InvalidInputError { msg })
+        }
+    }
+    /// Returns the number of addresses in this `CrossIpv6Pool`, both ends included,
+    /// saturating at `usize::MAX`.
+    pub fn len(&self) -> usize {
+        usize::try_from(self.end - self.start).map_or(usize::MAX, |n| n.saturating_add(1))
+    }
+    /// Returns all addresses of this `CrossIpv6Pool`, from the start to the end.
+    ///
+    /// This allocates one entry per address in the range, keep the range small.
+    pub fn to_vec(&self) -> Vec<Ipv6Addr> {
+        (self.start..=self.end).map(Ipv6Addr::from).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Ipv6Pool {
+    prefix: u128,
+    mask: u128,
+    next: u128,
+    stop: u128,
+}
+
+impl Iterator for Ipv6Pool {
+    type Item = Ipv6Addr;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next < self.stop {
+            let ret = self.prefix + self.next;
+            self.next += 1;
+            Some(ret.into())
+        } else {
+            None
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.stop - self.next) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // skip the n addresses without walking them
+        self.next = self.next.saturating_add(n as u128).min(self.stop);
+        self.next()
+    }
+}
+
+impl From<Ipv6Addr> for Ipv6Pool {
+    /// Returns the `/128` pool of this single address.
+    fn from(address: Ipv6Addr) -> Self {
+        Ipv6Pool::build(address.into(), IPV6_LEN)
+    }
+}
+
+impl fmt::Display for Ipv6Pool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let prefix: Ipv6Addr = self.prefix.into();
+        let prefix_len = self.prefix_len();
+        write!(f, "{}/{}", prefix, prefix_len)
+    }
+}
+
+impl PartialEq for Ipv6Pool {
+    /// Two pools are equal when they denote the same network, whatever their iteration position.
+    fn eq(&self, other: &Self) -> bool {
+        self.prefix == other.prefix && self.mask == other.mask
+    }
+}
+
+impl Eq for Ipv6Pool {}
+
+impl Hash for Ipv6Pool {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.prefix.hash(state);
+        self.mask.hash(state);
+    }
+}
+
+impl PartialOrd for Ipv6Pool {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ipv6Pool {
+    /// Orders by network address, then by prefix length with the more specific pool last.
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.prefix, self.mask).cmp(&(other.prefix, other.mask))
+    }
+}
+
+impl Ipv6Pool {
+    /// The whole IPv6 address space, `::/0`.
+    pub const FULL: Ipv6Pool = Ipv6Pool {
+        prefix: 0,
+        mask: 0,
+        next: INIT_NEXT_VALUE as u128,
+        // 2^128 does not fit in u128, the same as the /0 pool returned by build
+        stop: u128::MAX,
+    };
+    fn addr_check(ip_addr: &Ipv6Addr, prefix_len: u8) -> Result<(), SubnetworkErrors> {
+        if prefix_len > IPV6_LEN {
+            let error_addr = format!("{}/{}, {}", ip_addr, prefix_len, IPV6_CIDR_HINT);
+            Err(SubnetworkErrors::InvalidInputError {
+                msg: error_addr.to_string(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+    fn addr_check_str(address: &str) -> Result<(Ipv6Addr, u8), SubnetworkErrors> {
+        if address.contains("/") {
+            let address_vec: Vec<&str> = address.split("/").collect();
+            if address_vec.len() == 2 {
+                let addr_str = address_vec[0];
+                let bracketed = addr_str.starts_with('[');
+                // accept the bracketed URL form, e.g. [2001:db8::]/32
+                if bracketed == addr_str.ends_with(']') {
+                    let addr_str = if bracketed {
+                        &addr_str[1..addr_str.len() - 1]
+                    } else {
+                        addr_str
+                    };
+                    let addr = addr_str.parse::<Ipv6Addr>();
+                    let prefix_len = address_vec[1].parse::<u8>();
+                    if let (Ok(addr), Ok(prefix_len)) = (addr, prefix_len) {
+                        if prefix_len <= IPV6_LEN {
+                            return Ok((addr, prefix_len));
+                        }
+                    }
+                }
+            }
+        }
+        Err(SubnetworkErrors::InvalidInputError {
+            msg: format!("{}, {}", address, IPV6_CIDR_HINT),
+        })
+    }
+    fn build(addr: u128, prefix_len: u8) -> Ipv6Pool {
+        let mut mask: u128 = u128::MAX;
+        for _ in 0..(IPV6_LEN - prefix_len) {
+            mask <<= 1;
+        }
+        let exp = (IPV6_LEN - prefix_len) as u32;
+        let next = INIT_NEXT_VALUE as u128;
+        // 2^128 does not fit in u128, the /0 pool stops one address early
+        let stop = 1u128.checked_shl(exp).unwrap_or(u128::MAX);
+        let prefix = addr & mask;
+        Ipv6Pool {
+            prefix,
+            mask,
+            next,
+            stop,
+        }
+    }
+    /// Returns the pools which together with `self` exactly cover its ancestor
+    /// network of length `ancestor_len`, in ascending order.
+    fn exclude_from_ancestor(&self, ancestor_len: u8) -> Vec<Ipv6Pool> {
+        let prefix_len = self.prefix_len();
+        let mut ret = Vec::new();
+        for level in (ancestor_len + 1)..=prefix_len {
+            let level_mask = u128::MAX << (IPV6_LEN - level);
+            let sibling = (self.prefix & level_mask) ^ (1 << (IPV6_LEN - level));
+            ret.push(Ipv6Pool::build(sibling, level));
+        }
+        ret.sort_by_key(|p| p.prefix);
+        ret
+    }
+    /// Returns an Ipv6 iterator over the addresses contained in the network.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv6Pool;
+    /// use std::net::Ipv6Addr;
+    ///
+    /// fn main() {
+    ///     let ipv6_str = "::ffff:192.10.2.0";
+    ///     let ipv6: Ipv6Addr = ipv6_str.parse().unwrap();
+    ///     let ips = Ipv6Pool::new(ipv6, 120).unwrap();
+    ///     for i in ips {
+    ///         println!("{:?}", i);
+    ///     }
+    /// }
+    /// ```
+    pub fn new(address: Ipv6Addr, prefix_len: u8) -> Result<Ipv6Pool, SubnetworkErrors> {
+        match Ipv6Pool::addr_check(&address, prefix_len) {
+            Ok(_) => Ok(Ipv6Pool::build(address.into(), prefix_len)),
+            Err(e) => Err(e),
+        }
+    }
+    /// Returns an Ipv6 iterator over the addresses contained in the network.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv6Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv6Pool::from("::ffff:192.10.2.0/120").unwrap();
+    ///     for i in ips {
+    ///         println!("{:?}", i);
+    ///     }
+    /// }
+    /// ```
+    pub fn from(address: &str) -> Result<Ipv6Pool, SubnetworkErrors> {
+        match Ipv6Pool::addr_check_str(address) {
+            Ok((addr, prefix_len)) => Ok(Ipv6Pool::build(addr.into(), prefix_len)),
+            Err(e) => Err(e),
+        }
+    }
+    /// Check if ip pool contains this ip.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv6Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv6Pool::from("::ffff:192.10.2.0/120").unwrap();
+    ///     let ret = ips.contain_from_str("::ffff:192.10.2.1").unwrap();
+    ///     assert_eq!(ret, true);
+    /// }
+    /// ```
+    pub fn contain_from_str(&self, address: &str) -> Result<bool, SubnetworkErrors> {
+        match Ipv6Addr::from_str(address) {
+            Ok(addr) => {
+                let addr: u128 = addr.into();
+                if addr & self.mask == self.prefix {
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+    /// Check if ip pool contains this ip.
+    ///
+    /// # Example
+    /// ```
+    /// use std::net::Ipv6Addr;
+    /// use std::str::FromStr;
+    /// use subnetwork::Ipv6Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv6Pool::from("::ffff:192.10.2.0/120").unwrap();
+    ///     let ip = Ipv6Addr::from_str("::ffff:192.10.2.1").unwrap();
+    ///     let ret = ips.contain(ip);
+    ///     assert_eq!(ret, true);
+    /// }
+    /// ```
+    pub fn contain(&self, address: Ipv6Addr) -> bool {
+        let addr: u128 = address.into();
+        if addr & self.mask == self.prefix {
+            true
+        } else {
+            false
+        }
+    }
+    /// Returns the address of the network denoted by this `Ipv6Pool`.
+    /// This means the lowest possible IP address inside of the network.
+    pub fn network(&self) -> Ipv6Addr {
+        self.prefix.into()
+    }
+    /// Returns the highest address of this `Ipv6Pool`, IPv6 has no broadcast address.
+    ///
+    /// # Example
+    /// ```
+    /// use std::net::Ipv6Addr;
+    /// use subnetwork::Ipv6Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv6Pool::from("2001:db8::/120").unwrap();
+    ///     let last: Ipv6Addr = "2001:db8::ff".parse().unwrap();
+    ///     assert_eq!(ips.last_address(), last);
+    /// }
+    /// ```
+    pub fn last_address(&self) -> Ipv6Addr {
+        (self.prefix | !self.mask).into()
+    }
+    /// Returns the prefix length of this `Ipv6Pool`.
+    pub fn prefix_len(&self) -> u8 {
+        self.mask.count_ones() as u8
+    }
+    /// Returns the netmask of this `Ipv6Pool`, e.g. `ffff:ffff::` for a `/32`.
+    pub fn netmask(&self) -> Ipv6Addr {
+        self.mask.into()
+    }
+    /// Returns the addresses of this `Ipv6Pool` as an inclusive integer range,
+    /// from the network address to the highest address.
+    pub fn as_u128_range(&self) -> RangeInclusive<u128> {
+        self.prefix..=(self.prefix | !self.mask)
+    }
+    /// Returns this `Ipv6Pool` as a JSON object with the network, prefix and netmask.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"network\":\"{}\",\"prefix\":{},\"netmask\":\"{}\"}}",
+            self.network(),
+            self.prefix_len(),
+            self.netmask()
+        )
+    }
+    /// Returns the number of possible addresses in this `Ipv6Pool`, including the first
+    /// and the last one. Saturates at `usize::MAX` for short prefixes.
+    pub fn size(&self) -> usize {
+        usize::try_from(!self.mask).map_or(usize::MAX, |n| n.saturating_add(1))
+    }
+    /// Returns the number of addresses in this `Ipv6Pool`, including the first and the last one,
+    /// which is exactly the length of the `Vec` returned by `to_vec()`.
+    /// Saturates at `usize::MAX` for short prefixes.
+    pub fn len(&self) -> usize {
+        usize::try_from(!self.mask).map_or(usize::MAX, |n| n.saturating_add(1))
+    }
+    /// Returns `true` if this `Ipv6Pool` is a `/128`, holding a single address.
+    pub fn is_single_host(&self) -> bool {
+        self.mask == u128::MAX
+    }
+    /// Returns `true` if the prefix length of this `Ipv6Pool` falls on a nibble boundary
+    /// (a multiple of 4), which keeps the prefix readable in hex notation.
+    pub fn is_nibble_aligned(&self) -> bool {
+        self.mask.count_ones().is_multiple_of(4)
+    }
+    /// Returns `true` if this `Ipv6Pool` lies entirely within the IPv4-mapped range `::ffff:0:0/96`.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv6Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv6Pool::from("::ffff:192.10.2.0/120").unwrap();
+    ///     assert!(ips.is_ipv4_mapped());
+    /// }
+    /// ```
+    pub fn is_ipv4_mapped(&self) -> bool {
+        Ipv6Pool::build(0xffff_0000_0000, 96).contains_pool(self)
+    }
+    /// Returns the offset of `address` from the network address of this `Ipv6Pool`,
+    /// or `None` if the address is not in the pool.
+    pub fn offset_of(&self, address: Ipv6Addr) -> Option<u128> {
+        if self.contain(address) {
+            let addr: u128 = address.into();
+            Some(addr - self.prefix)
+        } else {
+            None
+        }
+    }
+    /// Returns the addresses right before and right after `address` in this `Ipv6Pool`,
+    /// `None` past the edges of the pool or if `address` is not in the pool.
+    pub fn neighbors(&self, address: Ipv6Addr) -> (Option<Ipv6Addr>, Option<Ipv6Addr>) {
+        if !self.contain(address) {
+            return (None, None);
+        }
+        let addr: u128 = address.into();
+        let last = self.prefix | !self.mask;
+        let prev = if addr > self.prefix {
+            Some((addr - 1).into())
+        } else {
+            None
+        };
+        let next = if addr < last {
+            Some((addr + 1).into())
+        } else {
+            None
+        };
+        (prev, next)
+    }
+    /// Returns all addresses of this `Ipv6Pool`, starting from the network address.
+    ///
+    /// This allocates one entry per address in the network, which is not feasible
+    /// for short prefixes (a `/64` alone holds 2^64 addresses). Use
+    /// [`Ipv6Pool::take_vec`] to materialize a bounded slice instead.
+    pub fn to_vec(&self) -> Vec<Ipv6Addr> {
+        (0..=!self.mask).map(|i| (self.prefix + i).into()).collect()
+    }
+    /// Returns at most `max` addresses of this `Ipv6Pool`, starting from the network address.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv6Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv6Pool::from("2001:db8::/64").unwrap();
+    ///     let preview = ips.take_vec(10);
+    ///     assert_eq!(preview.len(), 10);
+    /// }
+    /// ```
+    pub fn take_vec(&self, max: usize) -> Vec<Ipv6Addr> {
+        (0..=!self.mask)
+            .take(max)
+            .map(|i| (self.prefix + i).into())
+            .collect()
+    }
+    /// Returns an iterator over the addresses of this `Ipv6Pool` (from the network address
+    /// to the highest address) along with their reverse DNS (PTR) name.
+    pub fn iter_with_ptr(&self) -> impl Iterator<Item = (Ipv6Addr, String)> {
+        (self.prefix..=(self.prefix | !self.mask))
+            .map(|addr| (Ipv6Addr::from(addr), Ipv6 { addr }.reverse_pointer()))
+    }
+    /// Returns an iterator over the child prefixes of length `delegated_prefix`
+    /// contained in this `Ipv6Pool`, e.g. the `/64`s of a `/48`.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv6Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv6Pool::from("2001:db8::/60").unwrap();
+    ///     for child in ips.delegated_prefixes(64).unwrap() {
+    ///         println!("{}", child);
+    ///     }
+    /// }
+    /// ```
+    pub fn delegated_prefixes(
+        &self,
+        delegated_prefix: u8,
+    ) -> Result<impl Iterator<Item = Ipv6Pool>, SubnetworkErrors> {
+        let prefix_len = self.prefix_len();
+        if delegated_prefix < prefix_len || delegated_prefix > IPV6_LEN {
+            let network: Ipv6Addr = self.prefix.into();
+            let msg = format!(
+                "{}/{}, expected delegated prefix between {} and {}",
+                network, delegated_prefix, prefix_len, IPV6_LEN
+            );
+            return Err(SubnetworkErrors::InvalidInputError { msg });
+        }
+        let diff = (delegated_prefix - prefix_len) as u32;
+        let shift = (IPV6_LEN - delegated_prefix) as u32;
+        let last = u128::MAX.checked_shr(u128::BITS - diff).unwrap_or(0);
+        let prefix = self.prefix;
+        Ok((0..=last).map(move |i| {
+            let offset = i.checked_shl(shift).unwrap_or(0);
+            Ipv6Pool::build(prefix + offset, delegated_prefix)
+        }))
+    }
+    /// Returns a lazy iterator over the subnets of length `new_prefix` contained in this `Ipv6Pool`.
+    /// The subnets are produced on demand because there can be far too many to hold in memory,
+    /// e.g. the `/64`s of a `/32`.
+    pub fn subnets(
+        &self,
+        new_prefix: u8,
+    ) -> Result<impl Iterator<Item = Ipv6Pool>, SubnetworkErrors> {
+        self.delegated_prefixes(new_prefix)
+    }
+    /// Returns at most `max` of the first subnets of length `new_prefix` contained in this `Ipv6Pool`.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv6Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv6Pool::from("2001:db8::/32").unwrap();
+    ///     let preview = ips.subnets_preview(64, 3).unwrap();
+    ///     assert_eq!(preview.len(), 3);
+    /// }
+    /// ```
+    pub fn subnets_preview(
+        &self,
+        new_prefix: u8,
+        max: usize,
+    ) -> Result<Vec<Ipv6Pool>, SubnetworkErrors> {
+        Ok(self.subnets(new_prefix)?.take(max).collect())
+    }
+    /// Returns `true` if every address of `other` is contained in this `Ipv6Pool`.
+    pub fn contains_pool(&self, other: &Ipv6Pool) -> bool {
+        self.mask.count_ones() <= other.mask.count_ones() && other.prefix & self.mask == self.prefix
+    }
+    /// Returns the parent network of this `Ipv6Pool`, one prefix bit shorter,
+    /// or `None` for `/0`.
+    pub fn supernet(&self) -> Option<Ipv6Pool> {
+        match self.mask.count_ones() {
+            0 => None,
+            prefix_len => Some(Ipv6Pool::build(self.prefix, prefix_len as u8 - 1)),
+        }
+    }
+    /// Returns `true` if this `Ipv6Pool` fully contains `other`, the same as [`Ipv6Pool::contains_pool`].
+    pub fn supernet_of(&self, other: &Ipv6Pool) -> bool {
+        self.contains_pool(other)
+    }
+    /// Returns `true` if this `Ipv6Pool` and `other` share at least one address.
+    pub fn overlaps(&self, other: &Ipv6Pool) -> bool {
+        self.contains_pool(other) || other.contains_pool(self)
+    }
+    /// Returns `true` if this `Ipv6Pool` and `other` have no address in common.
+    pub fn is_disjoint(&self, other: &Ipv6Pool) -> bool {
+        !self.overlaps(other)
+    }
+    /// Returns the minimal set of pools covering the addresses of this `Ipv6Pool`
+    /// which are not in `other`, in ascending order.
+    pub fn exclude(&self, other: &Ipv6Pool) -> Vec<Ipv6Pool> {
+        if other.contains_pool(self) {
+            Vec::new()
+        } else if self.contains_pool(other) {
+            other.exclude_from_ancestor(self.prefix_len())
+        } else {
+            let prefix_len = self.prefix_len();
+            vec![Ipv6Pool::build(self.prefix, prefix_len)]
+        }
+    }
+    /// Returns the minimal set of pools covering the whole IPv6 address space
+    /// except this `Ipv6Pool`, in ascending order.
+    pub fn complement(&self) -> Vec<Ipv6Pool> {
+        Ipv6Pool::FULL.exclude(self)
+    }
+    /// Returns `true` if the prefix of this `Ipv6Pool` is longer than the prefix of `other`.
+    pub fn is_more_specific_than(&self, other: &Ipv6Pool) -> bool {
+        self.mask.count_ones() > other.mask.count_ones()
+    }
+    /// Returns `true` if the prefix of this `Ipv6Pool` is shorter than the prefix of `other`.
+    pub fn is_less_specific_than(&self, other: &Ipv6Pool) -> bool {
+        self.mask.count_ones() < other.mask.count_ones()
+    }
+}
+
+/* Pool List Functions */
+
+/// Returns `pools` sorted by network, without the pools contained in another one.
+fn remove_contained_ipv4(pools: &[Ipv4Pool]) -> Vec<Ipv4Pool> {
+    let mut sorted = pools.to_vec();
+    // a container sorts before the pools it contains
+    sorted.sort_by_key(|p| (p.prefix, p.mask));
+    let mut ret: Vec<Ipv4Pool> = Vec::new();
+    for pool in sorted {
+        match ret.last() {
+            Some(last) if last.contains_pool(&pool) => (),
+            _ => ret.push(pool),
+        }
+    }
+    ret
+}
+
+/// Returns `pools` sorted by network, without the pools contained in another one.
+fn remove_contained_ipv6(pools: &[Ipv6Pool]) -> Vec<Ipv6Pool> {
+    let mut sorted = pools.to_vec();
+    sorted.sort_by_key(|p| (p.prefix, p.mask));
+    let mut ret: Vec<Ipv6Pool> = Vec::new();
+    for pool in sorted {
+        match ret.last() {
+            Some(last) if last.contains_pool(&pool) => (),
+            _ => ret.push(pool),
+        }
+    }
+    ret
+}
+
+/// Returns the minimal sorted list of pools covering the same addresses as `pools`:
+/// contained pools are removed and adjacent sibling pools are merged into their parent.
+///
+/// # Example
+/// ```
+/// use subnetwork::{normalize_ipv4, Ipv4Pool};
+///
+/// fn main() {
+///     let pools = [
+///         Ipv4Pool::from("192.168.1.0/24").unwrap(),
+///         Ipv4Pool::from("192.168.0.0/24").unwrap(),
+///     ];
+///     let ret = normalize_ipv4(&pools);
+///     assert_eq!(ret.len(), 1);
+/// }
+/// ```
+pub fn normalize_ipv4(pools: &[Ipv4Pool]) -> Vec<Ipv4Pool> {
+    let mut ret: Vec<Ipv4Pool> = Vec::new();
+    for pool in remove_contained_ipv4(pools) {
+        ret.push(pool);
+        while ret.len() >= 2 {
+            let b = ret[ret.len() - 1];
+            let a = ret[ret.len() - 2];
+            let size = !a.mask + 1;
+            let siblings = a.mask == b.mask;
+            if siblings && a.prefix & size == 0 && a.prefix + size == b.prefix {
+                ret.truncate(ret.len() - 2);
+                ret.push(Ipv4Pool::build(a.prefix, a.mask.count_ones() as u8 - 1));
+            } else {
+                break;
+            }
+        }
+    }
+    ret
+}
+
+/// Collapses `pools` into the minimal set of covering CIDRs, the same as [`normalize_ipv4`].
+///
+/// # Example
+/// ```
+/// use subnetwork::{aggregate_ipv4, Ipv4Pool};
+///
+/// fn main() {
+///     let pools = [
+///         Ipv4Pool::from("192.168.0.0/24").unwrap(),
+///         Ipv4Pool::from("192.168.1.0/24").unwrap(),
+///     ];
+///     let ret = aggregate_ipv4(&pools);
+///     assert_eq!(ret, vec![Ipv4Pool::from("192.168.0.0/23").unwrap()]);
+/// }
+/// ```
+pub fn aggregate_ipv4(pools: &[Ipv4Pool]) -> Vec<Ipv4Pool> {
+    normalize_ipv4(pools)
+}
+
+/// Returns the same list as [`normalize_ipv4`] along with the number of input pools
+/// which were eliminated, either merged into a parent or contained in another pool.
+///
+/// # Example
+/// ```
+/// use subnetwork::{aggregate_ipv4_with_stats, Ipv4Pool};
+///
+/// fn main() {
+///     let pools = [
+///         Ipv4Pool::from("192.168.1.0/24").unwrap(),
+///         Ipv4Pool::from("192.168.0.0/24").unwrap(),
+///     ];
+///     let (ret, eliminated) = aggregate_ipv4_with_stats(&pools);
+///     println!("reduced {} routes to {}", ret.len() + eliminated, ret.len());
+/// }
+/// ```
+pub fn aggregate_ipv4_with_stats(pools: &[Ipv4Pool]) -> (Vec<Ipv4Pool>, usize) {
+    let ret = aggregate_ipv4(pools);
+    let eliminated = pools.len() - ret.len();
+    (ret, eliminated)
+}
+
+/// Returns the number of distinct addresses covered by `pools`, overlapping pools
+/// are only counted once and no address is materialized.
+///
+/// # Example
+/// ```
+/// use subnetwork::{union_count_ipv4, Ipv4Pool};
+///
+/// fn main() {
+///     let pools = [
+///         Ipv4Pool::from("192.168.0.0/23").unwrap(),
+///         Ipv4Pool::from("192.168.1.0/24").unwrap(),
+///     ];
+///     assert_eq!(union_count_ipv4(&pools), 512);
+/// }
+/// ```
+pub fn union_count_ipv4(pools: &[Ipv4Pool]) -> u128 {
+    normalize_ipv4(pools)
+        .iter()
+        .map(|p| p.address_count() as u128)
+        .sum()
+}
+
+/// Returns the minimal sorted list of pools covering the same addresses as `pools`:
+/// contained pools are removed and adjacent sibling pools are merged into their parent.
+pub fn normalize_ipv6(pools: &[Ipv6Pool]) -> Vec<Ipv6Pool> {
+    let mut ret: Vec<Ipv6Pool> = Vec::new();
+    for pool in remove_contained_ipv6(pools) {
+        ret.push(pool);
+        while ret.len() >= 2 {
+            let b = ret[ret.len() - 1];
+            let a = ret[ret.len() - 2];
+            let size = !a.mask + 1;
+            let siblings = a.mask == b.mask;
+            if siblings && a.prefix & size == 0 && a.prefix + size == b.prefix {
+                ret.truncate(ret.len() - 2);
+                ret.push(Ipv6Pool::build(a.prefix, a.mask.count_ones() as u8 - 1));
+            } else {
+                break;
+            }
+        }
+    }
+    ret
+}
+
+/// Returns the normalized `pools` of both families, the IPv4 pools first.
+/// Each family is normalized as `normalize_ipv4` and `normalize_ipv6` do.
+pub fn normalize_mixed(pools: &[IpPool]) -> Vec<IpPool> {
+    let mut ipv4_pools = Vec::new();
+    let mut ipv6_pools = Vec::new();
+    for pool in pools {
+        match pool {
+            IpPool::V4(p) => ipv4_pools.push(*p),
+            IpPool::V6(p) => ipv6_pools.push(*p),
+        }
+    }
+    let ipv4_pools = normalize_ipv4(&ipv4_pools).into_iter().map(IpPool::V4);
+    let ipv6_pools = normalize_ipv6(&ipv6_pools).into_iter().map(IpPool::V6);
+    ipv4_pools.chain(ipv6_pools).collect()
+}
+
+/// Returns the sum of the usable hosts of `pools` once normalized with [`normalize_ipv4`]:
+/// contained pools are only counted once and adjacent siblings count as their parent.
+///
+/// # Example
+/// ```
+/// use subnetwork::{total_usable_hosts_ipv4, Ipv4Pool};
+///
+/// fn main() {
+///     let pools = [
+///         Ipv4Pool::from("192.168.1.0/24").unwrap(),
+///         Ipv4Pool::from("192.168.1.0/25").unwrap(),
+///     ];
+///     assert_eq!(total_usable_hosts_ipv4(&pools), 254);
+/// }
+/// ```
+pub fn total_usable_hosts_ipv4(pools: &[Ipv4Pool]) -> u64 {
+    normalize_ipv4(pools).iter().map(|p| p.usable_hosts()).sum()
+}
+
+/// Returns the smallest `Ipv4Pool` containing both CIDR networks `a` and `b`.
+///
+/// # Example
+/// ```
+/// use subnetwork::common_supernet_str;
+///
+/// fn main() {
+///     let ips = common_supernet_str("192.168.0.0/24", "192.168.1.0/24").unwrap();
+///     println!("{}", ips);
+/// }
+/// ```
+pub fn common_supernet_str(a: &str, b: &str) -> Result<Ipv4Pool, SubnetworkErrors> {
+    let a = Ipv4Pool::from(a)?;
+    let b = Ipv4Pool::from(b)?;
+    let start = a.prefix.min(b.prefix);
+    let end = (a.prefix | !a.mask).max(b.prefix | !b.mask);
+    let range = CrossIpv4Pool {
+        start,
+        end,
+        next: start,
+    };
+    Ok(range.enclosing_pool())
+}
+
+/// Returns the index of the first pool of `others` sharing an address with `pool`,
+/// or `None` if `pool` overlaps none of them.
+///
+/// # Example
+/// ```
+/// use subnetwork::{overlaps_any_ipv4, Ipv4Pool};
+///
+/// fn main() {
+///     let pool = Ipv4Pool::from("192.168.1.0/25").unwrap();
+///     let others = [
+///         Ipv4Pool::from("10.0.0.0/8").unwrap(),
+///         Ipv4Pool::from("192.168.1.0/24").unwrap(),
+///     ];
+///     assert_eq!(overlaps_any_ipv4(&pool, &others), Some(1));
+/// }
+/// ```
+pub fn overlaps_any_ipv4(pool: &Ipv4Pool, others: &[Ipv4Pool]) -> Option<usize> {
+    others.iter().position(|p| p.overlaps(pool))
+}
+
+/// Returns the index of the first pool of `others` sharing an address with `pool`,
+/// or `None` if `pool` overlaps none of them.
+pub fn overlaps_any_ipv6(pool: &Ipv6Pool, others: &[Ipv6Pool]) -> Option<usize> {
+    others.iter().position(|p| p.overlaps(pool))
+}
+
+/// Returns the pool of `sorted_pools` containing `address` with a binary search,
+/// the pools must be sorted by network and must not overlap.
+///
+/// # Example
+/// ```
+/// use std::net::Ipv4Addr;
+/// use subnetwork::{search_containing_ipv4, Ipv4Pool};
+///
+/// fn main() {
+///     let pools = [
+///         Ipv4Pool::from("10.0.0.0/8").unwrap(),
+///         Ipv4Pool::from("192.168.1.0/24").unwrap(),
+///     ];
+///     let ret = search_containing_ipv4(&pools, Ipv4Addr::new(192, 168, 1, 1));
+///     assert!(ret.is_some());
+/// }
+/// ```
+pub fn search_containing_ipv4(sorted_pools: &[Ipv4Pool], address: Ipv4Addr) -> Option<&Ipv4Pool> {
+    sorted_pools
+        .binary_search_by(|p| p.position_of(address).reverse())
+        .ok()
+        .map(|i| &sorted_pools[i])
+}
+
+/// Returns the `Ipv4Pool` described by `network` and `mask`, or an error if the mask
+/// is not contiguous or `network` has bits set outside of the mask.
+///
+/// # Example
+/// ```
+/// use std::net::Ipv4Addr;
+/// use subnetwork::validate_network_mask_v4;
+///
+/// fn main() {
+///     let network = Ipv4Addr::new(192, 168, 1, 0);
+///     let mask = Ipv4Addr::new(255, 255, 255, 0);
+///     let ips = validate_network_mask_v4(network, mask).unwrap();
+///     println!("{}", ips);
+/// }
+/// ```
+pub fn validate_network_mask_v4(
+    network: Ipv4Addr,
+    mask: Ipv4Addr,
+) -> Result<Ipv4Pool, SubnetworkErrors> {
+    let addr: u32 = network.into();
+    let bits: u32 = mask.into();
+    if bits.leading_ones() + bits.trailing_zeros() != IPV4_LEN as u32 {
+        let msg = format!("{}, netmask is not contiguous", mask);
+        return Err(SubnetworkErrors::InvalidInputError { msg });
+    }
+    if addr & !bits != 0 {
+        let msg = format!("{} netmask {}, host bits are set", network, mask);
+        return Err(SubnetworkErrors::InvalidInputError { msg });
+    }
+    Ipv4Pool::new(network, bits.leading_ones() as u8)
+}
+
+/// Returns the canonical `network/prefix` form of the IPv4 or IPv6 CIDR `address`,
+/// with the host bits cleared and the IPv6 address compressed in lowercase.
+///
+/// # Example
+/// ```
+/// use subnetwork::canonicalize_cidr;
+///
+/// fn main() {
+///     assert_eq!(canonicalize_cidr("192.168.1.5/24").unwrap(), "192.168.1.0/24");
+///     assert_eq!(canonicalize_cidr("2001:DB8::1/32").unwrap(), "2001:db8::/32");
+/// }
+/// ```
+pub fn canonicalize_cidr(address: &str) -> Result<String, SubnetworkErrors> {
+    match address.parse::<IpPool>()? {
+        IpPool::V4(pool) => Ok(pool.to_ip_route_dst()),
+        IpPool::V6(pool) => Ok(format!("{}/{}", pool.network(), pool.prefix_len())),
+    }
+}
+
+/// Returns the longest prefix length whose IPv4 network holds at least `n` addresses
+/// (network and broadcast included), or an error if `n` is more than 2^32.
+///
+/// # Example
+/// ```
+/// use subnetwork::prefix_for_address_count_v4;
+///
+/// fn main() {
+///     assert_eq!(prefix_for_address_count_v4(300).unwrap(), 23);
+/// }
+/// ```
+pub fn prefix_for_address_count_v4(n: u64) -> Result<u8, SubnetworkErrors> {
+    if n > 1 << IPV4_LEN {
+        let msg = format!("{} addresses do not fit in any ipv4 network", n);
+        return Err(SubnetworkErrors::InvalidInputError { msg });
+    }
+    let bits = n.next_power_of_two().trailing_zeros() as u8;
+    Ok(IPV4_LEN - bits)
+}
+
+/// Returns the longest prefix length whose IPv6 network holds at least `n` addresses,
+/// every `u128` count fits in the whole IPv6 address space.
+pub fn prefix_for_address_count_v6(n: u128) -> u8 {
+    match n.checked_next_power_of_two() {
+        Some(size) => IPV6_LEN - size.trailing_zeros() as u8,
+        None => 0,
+    }
+}
+
+/* Mixed Family Pool */
+
+#[derive(Debug, Clone, Copy)]
+pub enum IpPool {
+    V4(Ipv4Pool),
+    V6(Ipv6Pool),
+}
+
+impl Iterator for IpPool {
+    type Item = IpAddr;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            IpPool::V4(pool) => pool.next().map(IpAddr::V4),
+            IpPool::V6(pool) => pool.next().map(IpAddr::V6),
+        }
+    }
+}
+
+impl FromStr for IpPool {
+    type Err = SubnetworkErrors;
+    /// Parses an IPv4 or an IPv6 CIDR, the family is picked from the address.
+    fn from_str(address: &str) -> Result<Self, Self::Err> {
+        if address.contains(':') {
+            Ok(IpPool::V6(Ipv6Pool::from(address)?))
+        } else {
+            Ok(IpPool::V4(Ipv4Pool::from(address)?))
+        }
+    }
+}
+
+impl IpPool {
+    /// Returns an `IpPool` of the same family as `address`.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::IpPool;
+    /// use std::net::IpAddr;
+    ///
+    /// fn main() {
+    ///     let ip: IpAddr = "192.168.1.1".parse().unwrap();
+    ///     let ips = IpPool::new(ip, 24).unwrap();
+    ///     println!("{:?}", ips);
+    /// }
+    /// ```
+    pub fn new(address: IpAddr, prefix_len: u8) -> Result<IpPool, SubnetworkErrors> {
+        match address {
+            IpAddr::V4(addr) => Ok(IpPool::V4(Ipv4Pool::new(addr, prefix_len)?)),
+            IpAddr::V6(addr) => Ok(IpPool::V6(Ipv6Pool::new(addr, prefix_len)?)),
+        }
+    }
+    /// Check if ip pool contains this ip, an address of the other family is never contained.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::IpPool;
+    ///
+    /// fn main() {
+    ///     let ips: IpPool = "2001:db8::/120".parse().unwrap();
+    ///     assert!(ips.contain("2001:db8::1".parse().unwrap()));
+    ///     assert!(!ips.contain("192.168.1.1".parse().unwrap()));
+    /// }
+    /// ```
+    pub fn contain(&self, address: IpAddr) -> bool {
+        match (self, address) {
+            (IpPool::V4(pool), IpAddr::V4(addr)) => pool.contain(addr),
+            (IpPool::V6(pool), IpAddr::V6(addr)) => pool.contain(addr),
+            _ => false,
+        }
+    }
+    /// Returns the address of the network denoted by this `IpPool`.
+    pub fn network(&self) -> IpAddr {
+        match self {
+            IpPool::V4(pool) => IpAddr::V4(pool.network()),
+            IpPool::V6(pool) => IpAddr::V6(pool.network()),
+        }
+    }
+}
+
+/* Single Addr Struct */
+
+#[derive(Debug, Clone, Copy)]
+pub struct Ipv4 {
+    addr: u32,
+}
+
+impl fmt::Display for Ipv4 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let addr: Ipv4Addr = self.addr.into();
+        write!(f, "{}", addr)
+    }
+}
+
+impl From<u32> for Ipv4 {
+    fn from(addr: u32) -> Self {
+        Ipv4 { addr }
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for Ipv4 {
+    fn from(octets: (u8, u8, u8, u8)) -> Self {
+        let (a, b, c, d) = octets;
+        Ipv4::new(Ipv4Addr::new(a, b, c, d))
+    }
+}
+
+impl TryFrom<IpAddr> for Ipv4 {
+    type Error = SubnetworkErrors;
+    fn try_from(address: IpAddr) -> Result<Self, Self::Error> {
+        match address {
+            IpAddr::V4(addr) => Ok(Ipv4::new(addr)),
+            IpAddr::V6(addr) => Err(SubnetworkErrors::InvalidInputError {
+                msg: format!("{}, {}", addr, IPV4_ADDR_HINT),
+            }),
+        }
+    }
+}
+
+impl From<Ipv4> for IpAddr {
+    fn from(address: Ipv4) -> Self {
+        IpAddr::V4(address.to_std())
+    }
+}
+
+impl Ipv4 {
+    fn prefix_len_check(&self, prefix_len: u8) -> Result<(), SubnetworkErrors> {
+        if prefix_len > IPV4_LEN {
+            let addr: Ipv4Addr = self.addr.into();
+            let error_msg = format!("{}/{}, {}", addr, prefix_len, IPV4_CIDR_HINT);
+            Err(SubnetworkErrors::InvalidInputError { msg: error_msg })
+        } else {
+            Ok(())
+        }
+    }
+    /// Constructs a new `Ipv4` from a given Ipv4Addr.
+    pub fn new(address: Ipv4Addr) -> Ipv4 {
+        // address: 192.168.1.1
+        let addr: u32 = address.into();
+        Ipv4 { addr }
+    }
+    /// Constructs a new `Ipv4` from a given `u32`.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4;
+    ///
+    /// fn main() {
+    ///     let ipv4 = Ipv4::from_u32(3232235777);
+    ///     assert_eq!(ipv4.to_string(), "192.168.1.1");
+    /// }
+    /// ```
+    pub fn from_u32(addr: u32) -> Ipv4 {
+        Ipv4 { addr }
+    }
+    /// Returns the standard IPv4 address of a 32-bit decimal number.
+    ///
+    /// # Example
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use subnetwork::Ipv4;
+    ///
+    /// fn main() {
+    ///     let ipv4 = Ipv4::from_decimal(3232235777);
+    ///     assert_eq!(ipv4, Ipv4Addr::new(192, 168, 1, 1));
+    /// }
+    /// ```
+    pub fn from_decimal(n: u32) -> Ipv4Addr {
+        n.into()
+    }
+    /// Constructs a new `Ipv4` from a given `&str`.
+    /// A string of digits only (no dots) is read as a 32-bit decimal number.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4;
+    ///
+    /// fn main() {
+    ///     let ipv4 = Ipv4::from("192.168.1.1").unwrap();
+    ///     for i in ipv4.iter(24).unwrap() {
+    ///         println!("{:?}", i);
+    ///     }
+    /// }
+    /// ```
+    pub fn from(address: &str) -> Result<Ipv4, SubnetworkErrors> {
+        // address: 3232235777
+        if !address.is_empty() && address.bytes().all(|b| b.is_ascii_digit()) {
+            let addr: u32 = address.parse()?;
+            return Ok(Ipv4 { addr });
+        }
+        // address: 192.168.1.1
+        match Ipv4Addr::from_str(address) {
+            Ok(addr) => {
+                let addr: u32 = addr.into();
+                Ok(Ipv4 { addr })
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+    pub fn iter(&self, prefix_len: u8) -> Result<Ipv4Pool, SubnetworkErrors> {
+        match self.prefix_len_check(prefix_len) {
+            Ok(_) => Ok(Ipv4Pool::build(self.addr, prefix_len)),
+            Err(e) => Err(e),
+        }
+    }
+    /// Returns the standard IPv4 address.
+    pub fn to_std(&self) -> Ipv4Addr {
+        self.addr.into()
+    }
+    /// Returns the largest identical prefix of two IP addresses.
+    /// # Example
+    /// ```
+    /// use subnetwork::{Ipv4, Ipv4Pool};
+    ///
+    /// fn main() {
+    ///     let ipv4_1 = Ipv4::from("192.168.1.136").unwrap();
+    ///     let ipv4_2 = Ipv4::from("192.168.1.192").unwrap();
+    ///     let ret = ipv4_1.largest_identical_prefix(ipv4_2);
+    ///     assert_eq!(ret, 25);
+    /// }
+    /// ```
+    pub fn largest_identical_prefix(&self, target: Ipv4) -> u32 {
+        let a = self.addr;
+        let b = target.addr;
+        let mut mask = 1;
+        for _ in 0..(IPV4_LEN - 1) {
+            mask <<= 1;
+        }
+        let mut count = 0;
+        for _ in 0..IPV4_LEN {
+            if a & mask != b & mask {
+                break;
+            }
+            count += 1;
+            mask >>= 1;
+        }
+        count
+    }
+    /// Returns the reverse DNS (PTR) name of this `Ipv4`.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4;
+    ///
+    /// fn main() {
+    ///     let ipv4 = Ipv4::from("192.168.1.1").unwrap();
+    ///     assert_eq!(ipv4.reverse_pointer(), "1.1.168.192.in-addr.arpa");
+    /// }
+    /// ```
+    pub fn reverse_pointer(&self) -> String {
+        let [a, b, c, d] = self.addr.to_be_bytes();
+        format!("{}.{}.{}.{}.in-addr.arpa", d, c, b, a)
+    }
+    /// Returns this `Ipv4` rounded down to a `/prefix_len` boundary (the host bits masked off).
+    ///
+    /// # Example
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use subnetwork::Ipv4;
+    ///
+    /// fn main() {
+    ///     let ipv4 = Ipv4::from("192.168.1.130").unwrap();
+    ///     assert_eq!(ipv4.floor_to_prefix(24), Ipv4Addr::new(192, 168, 1, 0));
+    ///     assert_eq!(ipv4.ceil_to_prefix(24), Ipv4Addr::new(192, 168, 2, 0));
+    /// }
+    /// ```
+    pub fn floor_to_prefix(&self, prefix_len: u8) -> Ipv4Addr {
+        let shift = (IPV4_LEN - prefix_len.min(IPV4_LEN)) as u32;
+        let mask = u32::MAX.checked_shl(shift).unwrap_or(0);
+        (self.addr & mask).into()
+    }
+    /// Returns this `Ipv4` rounded up to the next `/prefix_len` boundary,
+    /// saturating at `255.255.255.255`. An aligned address is returned unchanged.
+    pub fn ceil_to_prefix(&self, prefix_len: u8) -> Ipv4Addr {
+        let shift = (IPV4_LEN - prefix_len.min(IPV4_LEN)) as u32;
+        let mask = u32::MAX.checked_shl(shift).unwrap_or(0);
+        if self.addr & mask == self.addr {
+            self.addr.into()
+        } else {
+            (self.addr | !mask).saturating_add(1).into()
+        }
+    }
+    /// Returns the number of bits which differ between this `Ipv4` and `other`.
+    ///
+    /// # Example
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use subnetwork::Ipv4;
+    ///
+    /// fn main() {
+    ///     let ipv4 = Ipv4::from("192.168.1.0").unwrap();
+    ///     let ret = ipv4.bit_distance(Ipv4Addr::new(192, 168, 1, 7));
+    ///     assert_eq!(ret, 3);
+    /// }
+    /// ```
+    pub fn bit_distance(&self, other: Ipv4Addr) -> u32 {
+        let other: u32 = other.into();
+        (self.addr ^ other).count_ones()
+    }
+    fn in_block(&self, network: u32, prefix_len: u8) -> bool {
+        let mask = u32::MAX
+            .checked_shl((IPV4_LEN - prefix_len) as u32)
+            .unwrap_or(0);
+        self.addr & mask == network
+    }
+    /// Returns `true` if this `Ipv4` is in one of the private blocks of RFC 1918,
+    /// `10.0.0.0/8`, `172.16.0.0/12` or `192.168.0.0/16`.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4;
+    ///
+    /// fn main() {
+    ///     let ipv4 = Ipv4::from("172.20.1.1").unwrap();
+    ///     assert!(ipv4.is_private());
+    /// }
+    /// ```
+    pub fn is_private(&self) -> bool {
+        self.in_block(0x0a00_0000, 8)
+            || self.in_block(0xac10_0000, 12)
+            || self.in_block(0xc0a8_0000, 16)
+    }
+    /// Returns `true` if this `Ipv4` is in the loopback block `127.0.0.0/8`.
+    pub fn is_loopback(&self) -> bool {
+        self.in_block(0x7f00_0000, 8)
+    }
+    /// Returns `true` if this `Ipv4` is in the link local block `169.254.0.0/16`.
+    pub fn is_link_local(&self) -> bool {
+        self.in_block(0xa9fe_0000, 16)
+    }
+    /// Returns `true` if this `Ipv4` is in the multicast block `224.0.0.0/4`.
+    pub fn is_multicast(&self) -> bool {
+        self.in_block(0xe000_0000, 4)
+    }
+    /// Returns `true` if this `Ipv4` is in one of the documentation blocks of RFC 5737,
+    /// `192.0.2.0/24`, `198.51.100.0/24` or `203.0.113.0/24`.
+    pub fn is_documentation(&self) -> bool {
+        self.in_block(0xc000_0200, 24)
+            || self.in_block(0xc633_6400, 24)
+            || self.in_block(0xcb00_7100, 24)
+    }
+}
+
+/// The multicast addresses derived from a single `Ipv6`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MulticastSet {
+    pub node: Ipv6Addr,
+    pub link: Ipv6Addr,
+    pub site: Ipv6Addr,
+    pub solicited_node: Ipv6Addr,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Ipv6 {
+    addr: u128,
+}
+
+impl fmt::Display for Ipv6 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let addr: Ipv6Addr = self.addr.into();
+        write!(f, "{}", addr)
+    }
+}
+
+impl From<u128> for Ipv6 {
+    fn from(addr: u128) -> Self {
+        Ipv6 { addr }
+    }
+}
+
+impl From<(u16, u16, u16, u16, u16, u16, u16, u16)> for Ipv6 {
+    fn from(segments: (u16, u16, u16, u16, u16, u16, u16, u16)) -> Self {
+        let (a, b, c, d, e, f, g, h) = segments;
+        Ipv6::new(Ipv6Addr::new(a, b, c, d, e, f, g, h))
+    }
+}
+
+impl TryFrom<IpAddr> for Ipv6 {
+    type Error = SubnetworkErrors;
+    fn try_from(address: IpAddr) -> Result<Self, Self::Error> {
+        match address {
+            IpAddr::V6(addr) => Ok(Ipv6::new(addr)),
+            IpAddr::V4(addr) => Err(SubnetworkErrors::InvalidInputError {
+                msg: format!("{}, {}", addr, IPV6_ADDR_HINT),
+            }),
+        }
+    }
+}
+
+impl From<Ipv6> for IpAddr {
+    fn from(address: Ipv6) -> Self {
+        IpAddr::V6(address.to_std())
+    }
+}
+
+impl Ipv6 {
+    fn prefix_len_check(&self, prefix_len: u8) -> Result<(), SubnetworkErrors> {
+        if prefix_len > IPV6_LEN {
+            let addr: Ipv6Addr = self.addr.into();
+            let msg = format!("{}/{}, {}", addr, prefix_len, IPV6_CIDR_HINT);
+            Err(SubnetworkErrors::InvalidInputError { msg })
+        } else {
+            Ok(())
+        }
+    }
+    /// Constructs a new `Ipv6` from a given Ipv6Addr.
+    pub fn new(address: Ipv6Addr) -> Ipv6 {
+        let addr: u128 = address.into();
+        Ipv6 { addr }
+    }
+    /// Constructs a new `Ipv6` from a given `&str`.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv6;
+    ///
+    /// fn main() {
+    ///     let ipv6 = Ipv6::from("::ffff:192.10.2.255").unwrap();
+    ///     for i in ipv6.iter(124) {
+    ///         println!("{:?}", i);
+    ///     }
+    /// }
+    /// ```
+    pub fn from(address: &str) -> Result<Ipv6, SubnetworkErrors> {
+        match Ipv6Addr::from_str(address) {
+            Ok(addr) => {
+                let addr: u128 = addr.into();
+                Ok(Ipv6 { addr })
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+    /// Returns an Ipv6 iterator over the addresses contained in the network.
+    pub fn iter(&self, prefix_len: u8) -> Result<Ipv6Pool, SubnetworkErrors> {
+        match self.prefix_len_check(prefix_len) {
+            Ok(_) => Ok(Ipv6Pool::build(self.addr, prefix_len)),
+            Err(e) => Err(e),
+        }
+    }
+    fn scope_multicast(&self, scope: u16) -> Ipv6Addr {
+        let group = Ipv6Addr::new(
+            scope, 0x0000, 0x0000, 0x0000, 0x0000, 0x0001, 0xFF00, 0x0000,
+        );
+        let group = Ipv6::new(group);
+        let mask = Ipv6Addr::new(
+            0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x00FF, 0xFFFF,
+        );
+        let mask = Ipv6::new(mask);
+        (group.addr + (mask.addr & self.addr)).into()
+    }
+    /// Returns the node local scope multicast address of this `Ipv6`.
+    pub fn node_multicast(&self) -> Ipv6Addr {
+        self.scope_multicast(0xFF01)
+    }
+    /// Returns the link local scope multicast address of this `Ipv6`.
+    pub fn link_multicast(&self) -> Ipv6Addr {
+        self.scope_multicast(0xFF02)
+    }
+    /// Returns the site local scope multicast address of this `Ipv6`.
+    pub fn site_multicast(&self) -> Ipv6Addr {
+        self.scope_multicast(0xFF05)
+    }
+    /// Returns the solicited-node multicast address of this `Ipv6` (RFC 4291),
+    /// which is the same group as the link local scope multicast address.
+    pub fn solicited_node_multicast(&self) -> Ipv6Addr {
+        self.link_multicast()
+    }
+    /// Returns the low 24 bits of this `Ipv6`, which are the bits the multicast
+    /// addresses above copy from the unicast address.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv6;
+    ///
+    /// fn main() {
+    ///     let ipv6 = Ipv6::from("ff02::1:ff0a:2ff").unwrap();
+    ///     assert_eq!(ipv6.multicast_low24(), 0x0a02ff);
+    /// }
+    /// ```
+    pub fn multicast_low24(&self) -> u32 {
+        (self.addr & 0x00FF_FFFF) as u32
+    }
+    /// Returns all the multicast addresses of this `Ipv6` at once.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv6;
+    ///
+    /// fn main() {
+    ///     let ipv6 = Ipv6::from("::ffff:192.10.2.255").unwrap();
+    ///     let set = ipv6.all_multicast();
+    ///     println!("{:?}", set);
+    /// }
+    /// ```
+    pub fn all_multicast(&self) -> MulticastSet {
+        MulticastSet {
+            node: self.node_multicast(),
+            link: self.link_multicast(),
+            site: self.site_multicast(),
+            solicited_node: self.solicited_node_multicast(),
+        }
+    }
+    /// Returns the standard IPv4 address.
+    pub fn to_std(&self) -> Ipv6Addr {
+        self.addr.into()
+    }
+    pub fn max_identical_prefix(&self, target: Ipv6) -> u128 {
+        let a = self.addr;
+        let b = target.addr;
+        let mut mask = 1;
+        for _ in 0..(IPV6_LEN - 1) {
+            mask <<= 1;
+        }
+        let mut count = 0;
+        for _ in 0..IPV6_LEN {
+            if a & mask != b & mask {
+                break;
+            }
+            count += 1;
+            mask >>= 1;
+        }
+        count - 1
+    }
+    /// Returns the reverse DNS (PTR) name of this `Ipv6`, one label per nibble.
+    pub fn reverse_pointer(&self) -> String {
+        let mut ret = String::with_capacity(72);
+        for i in 0..32 {
+            let nibble = (self.addr >> (i * 4)) & 0xF;
+            ret.push_str(&format!("{:x}.", nibble));
+        }
+        ret.push_str("ip6.arpa");
+        ret
+    }
+    /// Returns this `Ipv6` rounded down to a `/prefix_len` boundary (the host bits masked off).
+    pub fn floor_to_prefix(&self, prefix_len: u8) -> Ipv6Addr {
+        let shift = (IPV6_LEN - prefix_len.min(IPV6_LEN)) as u32;
+        let mask = u128::MAX.checked_shl(shift).unwrap_or(0);
+        (self.addr & mask).into()
+    }
+    /// Returns this `Ipv6` rounded up to the next `/prefix_len` boundary,
+    /// saturating at the highest address. An aligned address is returned unchanged.
+    pub fn ceil_to_prefix(&self, prefix_len: u8) -> Ipv6Addr {
+        let shift = (IPV6_LEN - prefix_len.min(IPV6_LEN)) as u32;
+        let mask = u128::MAX.checked_shl(shift).unwrap_or(0);
+        if self.addr & mask == self.addr {
+            self.addr.into()
+        } else {
+            (self.addr | !mask).saturating_add(1).into()
+        }
+    }
+    /// Returns the number of bits which differ between this `Ipv6` and `other`.
+    pub fn bit_distance(&self, other: Ipv6Addr) -> u32 {
+        let other: u128 = other.into();
+        (self.addr ^ other).count_ones()
+    }
+}
+
+/* Serde Support */
+
+#[cfg(feature = "serde")]
+fn parse_range<T: FromStr>(range: &str) -> Option<(T, T)> {
+    let (start, end) = range.split_once('-')?;
+    Some((start.trim().parse().ok()?, end.trim().parse().ok()?))
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Ipv4Pool {
+    /// Serializes to the CIDR form, e.g. `192.168.1.0/24`.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_ip_route_dst())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Ipv4Pool {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let address = String::deserialize(deserializer)?;
+        Ipv4Pool::from(&address).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Ipv6Pool {
+    /// Serializes to the CIDR form, e.g. `2001:db8::/32`.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let address = format!("{}/{}", self.network(), self.prefix_len());
+        serializer.serialize_str(&address)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Ipv6Pool {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let address = String::deserialize(deserializer)?;
+        Ipv6Pool::from(&address).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CrossIpv4Pool {
+    /// Serializes to the range form, e.g. `192.168.1.1-192.168.1.10`.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let start: Ipv4Addr = self.start.into();
+        let end: Ipv4Addr = self.end.into();
+        serializer.serialize_str(&format!("{}-{}", start, end))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CrossIpv4Pool {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let range = String::deserialize(deserializer)?;
+        match parse_range(&range) {
+            Some((start, end)) => CrossIpv4Pool::new(start, end).map_err(serde::de::Error::custom),
+            None => Err(serde::de::Error::custom(format!(
+                "{}, expected 'A.B.C.D-A.B.C.D'",
+                range
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CrossIpv6Pool {
+    /// Serializes to the range form, e.g. `2001:db8::1-2001:db8::10`.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let start: Ipv6Addr = self.start.into();
+        let end: Ipv6Addr = self.end.into();
+        serializer.serialize_str(&format!("{}-{}", start, end))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CrossIpv6Pool {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let range = String::deserialize(deserializer)?;
+        match parse_range(&range) {
+            Some((start, end)) => CrossIpv6Pool::new(start, end).map_err(serde::de::Error::custom),
+            None => Err(serde::de::Error::custom(format!(
+                "{}, expected 'X:X::X-X:X::X'",
+                range
+            ))),
+        }
+    }
+}
+
+/* ipnet Support */
+
+#[cfg(feature = "ipnet")]
+impl From<ipnet::Ipv4Net> for Ipv4Pool {
+    fn from(net: ipnet::Ipv4Net) -> Self {
+        Ipv4Pool::build(net.network().into(), net.prefix_len())
+    }
+}
+
+#[cfg(feature = "ipnet")]
+impl From<Ipv4Pool> for ipnet::Ipv4Net {
+    fn from(pool: Ipv4Pool) -> Self {
+        // the prefix length of a pool is never above 32
+        ipnet::Ipv4Net::new(pool.network(), pool.prefix_len()).unwrap()
+    }
+}
+
+#[cfg(feature = "ipnet")]
+impl From<ipnet::Ipv6Net> for Ipv6Pool {
+    fn from(net: ipnet::Ipv6Net) -> Self {
+        Ipv6Pool::build(net.network().into(), net.prefix_len())
+    }
+}
+
+#[cfg(feature = "ipnet")]
+impl From<Ipv6Pool> for ipnet::Ipv6Net {
+    fn from(pool: Ipv6Pool) -> Self {
+        // the prefix length of a pool is never above 128
+        ipnet::Ipv6Net::new(pool.network(), pool.prefix_len()).unwrap()
+    }
+}
+
+/* rand Support */
+
+#[cfg(feature = "rand")]
+impl Ipv4Pool {
+    /// Returns an address drawn uniformly from this `Ipv4Pool`. Every address of the pool
+    /// can be drawn, including the network and broadcast addresses.
+    ///
+    /// # Example
+    /// ```
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+    ///     let ret = ips.random_host(&mut rand::rng());
+    ///     assert!(ips.contain(ret));
+    /// }
+    /// ```
+    pub fn random_host(&self, rng: &mut impl rand::Rng) -> Ipv4Addr {
+        let offset = rand::RngExt::random_range(rng, 0..self.address_count());
+        (self.prefix + offset as u32).into()
+    }
+    /// Returns an iterator over `n` addresses drawn independently with [`Ipv4Pool::random_host`].
+    pub fn random_hosts<'a, R: rand::Rng>(
+        &self,
+        rng: &'a mut R,
+        n: usize,
+    ) -> impl Iterator<Item = Ipv4Addr> + 'a {
+        let pool = *self;
+        (0..n).map(move |_| pool.random_host(rng))
+    }
+}
+
+/* rayon Support */
+
+#[cfg(feature = "rayon")]
+impl rayon::iter::IntoParallelIterator for Ipv4Pool {
+    type Iter = rayon::iter::MapWith<
+        rayon::iter::Chain<rayon::range::Iter<u32>, rayon::option::IntoIter<u32>>,
+        u32,
+        fn(&mut u32, u32) -> Ipv4Addr,
+    >;
+    type Item = Ipv4Addr;
+    /// Yields the addresses this `Ipv4Pool` has not iterated yet, in parallel.
+    fn into_par_iter(self) -> Self::Iter {
+        use rayon::iter::ParallelIterator;
+        // the offsets of a /0 go up to 2^32, split off the last one to stay in an indexed u32 range
+        let last = u32::MAX as u64;
+        let range = self.next.min(last) as u32..self.stop.min(last) as u32;
+        let tail = (self.next <= last && self.stop > last).then_some(u32::MAX);
+        range
+            .into_par_iter()
+            .chain(tail)
+            .map_with(self.prefix, |prefix, i| (*prefix + i).into())
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl Ipv4Pool {
+    /// Returns a parallel iterator over all the addresses of this `Ipv4Pool`,
+    /// the same addresses as [`Ipv4Pool::to_vec`].
+    ///
+    /// # Example
+    /// ```
+    /// use rayon::prelude::*;
+    /// use subnetwork::Ipv4Pool;
+    ///
+    /// fn main() {
+    ///     let ips = Ipv4Pool::from("192.168.0.0/16").unwrap();
+    ///     let ret = ips.par_iter().filter(|ip| ip.octets()[3] == 1).count();
+    ///     assert_eq!(ret, 256);
+    /// }
+    /// ```
+    pub fn par_iter(&self) -> impl rayon::iter::IndexedParallelIterator<Item = Ipv4Addr> {
+        use rayon::iter::IntoParallelIterator;
+        Ipv4Pool::build(self.prefix, self.prefix_len()).into_par_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::boxed::Box;
+    use alloc::collections::BTreeSet;
+    use std::collections::HashSet;
+    /* cross ipv4 pool */
+    #[test]
+    fn cross_ipv4_pool_print() {
+        let start = Ipv4Addr::new(192, 168, 1, 1);
+        let end = Ipv4Addr::new(192, 168, 3, 254);
+        let ips = CrossIpv4Pool::new(start, end).unwrap();
+        for i in ips {
+            println!("{:?}", i);
+        }
+    }
+    /* ipv4 test */
+    #[test]
+    fn ipv4_pool_print() {
+        let test_str = "192.168.1.0/24";
+        let ipv4_pool = Ipv4Pool::from(test_str).unwrap();
+        let ipv4_pool_str = format!("{}", ipv4_pool);
+        println!("{}", ipv4_pool_str);
+    }
+    #[test]
+    fn ipv4_print() {
+        let test_str = "192.168.1.1";
+        let ipv4 = Ipv4::from(test_str).unwrap();
+        let ipv4_str = format!("{}", ipv4);
+        assert_eq!(ipv4_str, test_str);
+    }
+    #[test]
+    fn ipv4_iter() {
+        let ipv4 = Ipv4::from("192.168.1.1").unwrap();
+        for i in ipv4.iter(24).unwrap() {
+            println!("{:?}", i);
+        }
+        assert_eq!(1, 1);
+    }
+    #[test]
+    fn ipv6_iter() {
+        let ipv6 = Ipv6::from("::ffff:192.10.2.255").unwrap();
+        for i in ipv6.iter(124).unwrap() {
+            println!("{:?}", i);
+        }
+        assert_eq!(1, 1);
+    }
+    #[test]
+    fn ipv4() {
+        let ipv4 = Ipv4::from("192.168.1.1").unwrap();
+        println!("{:8b}", ipv4.addr);
+        assert_eq!(ipv4.addr, 3232235777);
+    }
+    /* ipv6 test */
+    #[test]
+    fn ipv6() {
+        let ipv6 = Ipv6::from("::ffff:192.10.2.255").unwrap();
+        println!("{:?}", ipv6);
+        assert_eq!(ipv6.addr, 281473903624959);
+    }
+    #[test]
+    fn ipv6_node() {
+        // let a: u8 = 0b1100;
+        // let b: u8 = 0b0011;
+        // println!("{}", a + b);
+        let ipv6 = Ipv6::from("::ffff:192.10.2.255").unwrap();
+        let ipv6_2: Ipv6Addr = "ff01::1:ff0a:2ff".parse().unwrap();
+        println!("{:?}", ipv6.node_multicast());
+        assert_eq!(ipv6.node_multicast(), ipv6_2);
+    }
+    #[test]
+    fn ipv6_link() {
+        let ipv6 = Ipv6::from("::ffff:192.10.2.255").unwrap();
+        let ipv6_2: Ipv6Addr = "ff02::1:ff0a:2ff".parse().unwrap();
+        println!("{:?}", ipv6.link_multicast());
+        assert_eq!(ipv6.link_multicast(), ipv6_2);
+    }
+    /* ipv4 pool test */
+    #[test]
+    fn ipv4_pool() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        for i in ips {
+            println!("{:?}", i);
+        }
+        assert_eq!(1, 1);
+    }
+    #[test]
+    fn ipv4_pool_new() {
+        let ip = Ipv4Addr::new(192, 168, 1, 1);
+        let ips = Ipv4Pool::new(ip, 24).unwrap();
+        for i in ips {
+            println!("{:?}", i);
+        }
+        assert_eq!(1, 1);
+    }
+    #[test]
+    fn ipv4_pool_contain_1() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let ret = ips.contain_from_str("192.168.1.20").unwrap();
+        println!("{:?}", ret);
+        assert_eq!(ret, true);
+    }
+    #[test]
+    fn ipv4_pool_contain_2() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let ret = ips.contain_from_str("10.8.0.20").unwrap();
+        println!("{:?}", ret);
+        assert_eq!(ret, false);
+    }
+    #[test]
+    fn ipv4_pool_network() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let network = ips.network();
+        let network_2 = Ipv4Addr::new(192, 168, 1, 0);
+        println!("{:?}", network);
+        assert_eq!(network, network_2);
+    }
+    #[test]
+    fn ipv4_pool_broadcast() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let broadcast = ips.broadcast();
+        let broadcast_2 = Ipv4Addr::new(192, 168, 1, 255);
+        println!("{:?}", broadcast);
+        assert_eq!(broadcast, broadcast_2);
+    }
+    #[test]
+    fn ipv4_pool_size() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let size = ips.size();
+        println!("{:?}", size);
+        assert_eq!(size, 256);
+    }
+    #[test]
+    fn ipv4_pool_len() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let size = ips.len();
+        println!("{:?}", size);
+        assert_eq!(size, 256);
+    }
+    #[test]
+    fn test_largest_identical_prefix() {
+        let ipv4_1 = Ipv4::from("192.168.1.136").unwrap();
+        let ipv4_2 = Ipv4::from("192.168.1.192").unwrap();
+        let ret = ipv4_1.largest_identical_prefix(ipv4_2);
+        println!("{}", ret);
+    }
+    #[test]
+    fn test_max_idt() {
+        let a: u32 = 14;
+        let b: u32 = 12;
+        let mut mask = 1;
+        for _ in 0..31 {
+            mask <<= 1;
+        }
+        println!("{}", mask);
+
+        let mut count = 0;
+        for _ in 0..32 {
+            if a & mask != b & mask {
+                break;
+            }
+            count += 1;
+            mask >>= 1;
+        }
+        println!("{}", count);
+    }
+    #[test]
+    // #[should_panic]
+    fn test_github_issues_1() {
+        let _pool1 = Ipv4Pool::from("1.2.3.4/33");
+        let _pool2 = Ipv4Pool::from("1.2.3.4/");
+        let _pool3 = Ipv4Pool::from("nonip/24");
+    }
+    #[test]
+    fn ipv4_pool_offset_of() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let offset = ips.offset_of(ips.network());
+        assert_eq!(offset, Some(0));
+        let offset = ips.offset_of(ips.broadcast());
+        assert_eq!(offset, Some(ips.size() as u32 - 1));
+        let offset = ips.offset_of(Ipv4Addr::new(10, 8, 0, 20));
+        assert_eq!(offset, None);
+    }
+    #[test]
+    fn ipv6_pool_offset_of() {
+        let ips = Ipv6Pool::from("::ffff:192.10.2.0/120").unwrap();
+        let offset = ips.offset_of(ips.network());
+        assert_eq!(offset, Some(0));
+        let last: Ipv6Addr = "::ffff:192.10.2.255".parse().unwrap();
+        let offset = ips.offset_of(last);
+        assert_eq!(offset, Some(ips.size() as u128 - 1));
+        let outside: Ipv6Addr = "::ffff:192.10.3.1".parse().unwrap();
+        let offset = ips.offset_of(outside);
+        assert_eq!(offset, None);
+    }
+    #[test]
+    fn ipv4_pool_specific() {
+        let ips_1 = Ipv4Pool::from("192.168.1.0/25").unwrap();
+        let ips_2 = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        assert!(ips_1.is_more_specific_than(&ips_2));
+        assert!(!ips_1.is_less_specific_than(&ips_2));
+        assert!(ips_2.is_less_specific_than(&ips_1));
+        assert!(!ips_2.is_more_specific_than(&ips_1));
+        assert!(!ips_1.is_more_specific_than(&ips_1));
+    }
+    #[test]
+    fn ipv6_pool_specific() {
+        let ips_1 = Ipv6Pool::from("2001:db8::/33").unwrap();
+        let ips_2 = Ipv6Pool::from("2001:db8::/32").unwrap();
+        assert!(ips_1.is_more_specific_than(&ips_2));
+        assert!(ips_2.is_less_specific_than(&ips_1));
+    }
+    #[test]
+    fn ipv6_pool_to_vec() {
+        let ips = Ipv6Pool::from("::ffff:192.10.2.0/124").unwrap();
+        let addrs = ips.to_vec();
+        assert_eq!(addrs.len(), 16);
+        assert_eq!(addrs[0], ips.network());
+    }
+    #[test]
+    fn ipv6_pool_take_vec() {
+        let ips = Ipv6Pool::from("2001:db8::/64").unwrap();
+        let addrs = ips.take_vec(10);
+        println!("{:?}", addrs);
+        assert_eq!(addrs.len(), 10);
+        assert_eq!(addrs[0], ips.network());
+        let last: Ipv6Addr = "2001:db8::9".parse().unwrap();
+        assert_eq!(addrs[9], last);
+        let small = Ipv6Pool::from("2001:db8::/126").unwrap();
+        assert_eq!(small.take_vec(10).len(), 4);
+    }
+    #[test]
+    fn error_message_hint() {
+        let err = Ipv4Pool::from("1.2.3.4/33").unwrap_err();
+        let msg = err.to_string();
+        println!("{}", msg);
+        assert!(msg.contains("1.2.3.4/33"));
+        assert!(msg.contains("expected 'A.B.C.D/prefix'"));
+        let err = Ipv4Pool::new(Ipv4Addr::new(1, 2, 3, 4), 40).unwrap_err();
+        assert!(err.to_string().contains("expected 'A.B.C.D/prefix'"));
+        let err = Ipv6Pool::from("2001:db8::/129").unwrap_err();
+        assert!(err.to_string().contains("expected 'X:X::X/prefix'"));
+        let err = Ipv4Pool::from("nonip/24").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("nonip/24, expected 'A.B.C.D/prefix'"));
+        let err = Ipv4Pool::from("1.2.3.4/").unwrap_err();
+        assert!(err.to_string().contains("expected 'A.B.C.D/prefix'"));
+        let err = Ipv6Pool::from("nonip/64").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("nonip/64, expected 'X:X::X/prefix'"));
+        let start = Ipv4Addr::new(192, 168, 3, 1);
+        let end = Ipv4Addr::new(192, 168, 1, 1);
+        let err = CrossIpv4Pool::new(start, end).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected start address <= end address"));
+    }
+    #[test]
+    fn ipv6_pool_delegated_prefixes() {
+        let ips = Ipv6Pool::from("2001:db8::/60").unwrap();
+        let children: Vec<Ipv6Pool> = ips.delegated_prefixes(64).unwrap().collect();
+        assert_eq!(children.len(), 16);
+        let first: Ipv6Addr = "2001:db8::".parse().unwrap();
+        let last: Ipv6Addr = "2001:db8:0:f::".parse().unwrap();
+        assert_eq!(children[0].network(), first);
+        assert_eq!(children[15].network(), last);
+        assert_eq!(children[15].to_string(), "2001:db8:0:f::/64");
+        let same: Vec<Ipv6Pool> = ips.delegated_prefixes(60).unwrap().collect();
+        assert_eq!(same.len(), 1);
+        assert!(ips.delegated_prefixes(56).is_err());
+        assert!(ips.delegated_prefixes(129).is_err());
+    }
+    /* ip pool test */
+    #[test]
+    fn ip_pool_new() {
+        let ip: IpAddr = "192.168.1.1".parse().unwrap();
+        let ips = IpPool::new(ip, 24).unwrap();
+        match ips {
+            IpPool::V4(pool) => assert_eq!(pool.network(), Ipv4Addr::new(192, 168, 1, 0)),
+            IpPool::V6(_) => panic!("expected an ipv4 pool"),
+        }
+        let ip: IpAddr = "2001:db8::1".parse().unwrap();
+        let ips = IpPool::new(ip, 64).unwrap();
+        match ips {
+            IpPool::V4(_) => panic!("expected an ipv6 pool"),
+            IpPool::V6(pool) => {
+                let network: Ipv6Addr = "2001:db8::".parse().unwrap();
+                assert_eq!(pool.network(), network);
+            }
+        }
+        let ip: IpAddr = "192.168.1.1".parse().unwrap();
+        assert!(IpPool::new(ip, 64).is_err());
+        let ip: IpAddr = "2001:db8::1".parse().unwrap();
+        assert!(IpPool::new(ip, 129).is_err());
+    }
+    #[test]
+    fn ipv4_pool_gateway() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let (network, gateway) = ips.network_and_gateway();
+        assert_eq!(network, Ipv4Addr::new(192, 168, 1, 0));
+        assert_eq!(gateway, Ipv4Addr::new(192, 168, 1, 1));
+        assert_eq!(ips.gateway(), gateway);
+        let ips = Ipv4Pool::from("192.168.1.7/32").unwrap();
+        assert_eq!(ips.gateway(), Ipv4Addr::new(192, 168, 1, 7));
+    }
+    #[test]
+    fn ip_pool_iter() {
+        let ip: IpAddr = "192.168.1.0".parse().unwrap();
+        let ips = IpPool::new(ip, 30).unwrap();
+        let mut count = 0;
+        for i in ips {
+            println!("{:?}", i);
+            assert!(i.is_ipv4());
+            count += 1;
+        }
+        assert_eq!(count, Ipv4Pool::from("192.168.1.0/30").unwrap().count());
+        let ip: IpAddr = "2001:db8::".parse().unwrap();
+        let ips = IpPool::new(ip, 126).unwrap();
+        let mut count = 0;
+        for i in ips {
+            println!("{:?}", i);
+            assert!(i.is_ipv6());
+            count += 1;
+        }
+        assert_eq!(count, Ipv6Pool::from("2001:db8::/126").unwrap().count());
+    }
+    #[test]
+    fn ipv4_pool_exclude() {
+        let ips = Ipv4Pool::from("192.168.0.0/22").unwrap();
+        let other = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let ret = ips.exclude(&other);
+        let ret: Vec<String> = ret
+            .iter()
+            .map(|p| format!("{}/{}", p.network(), p.mask.count_ones()))
+            .collect();
+        assert_eq!(ret, vec!["192.168.0.0/24", "192.168.2.0/23"]);
+        assert!(other.exclude(&ips).is_empty());
+        let disjoint = Ipv4Pool::from("10.0.0.0/8").unwrap();
+        assert_eq!(ips.exclude(&disjoint).len(), 1);
+    }
+    #[test]
+    fn ipv4_pool_complement() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let mut ret = ips.complement();
+        assert_eq!(ret.len(), 24);
+        ret.push(ips);
+        let total: u64 = ret.iter().map(|p| !p.mask as u64 + 1).sum();
+        assert_eq!(total, 1 << 32);
+        assert_eq!(Ipv4Pool::FULL, Ipv4Pool::from("0.0.0.0/0").unwrap());
+        assert_eq!(
+            Ipv4Pool::FULL.len(),
+            Ipv4Pool::from("0.0.0.0/0").unwrap().len()
+        );
+        assert!(Ipv4Pool::FULL.complement().is_empty());
+        for (i, a) in ret.iter().enumerate() {
+            for b in &ret[i + 1..] {
+                assert!(!a.contains_pool(b) && !b.contains_pool(a));
+            }
+        }
+    }
+    #[test]
+    fn ipv6_pool_complement() {
+        let ips = Ipv6Pool::from("2001:db8::/32").unwrap();
+        let mut ret = ips.complement();
+        assert_eq!(ret.len(), 32);
+        ret.push(ips);
+        // count in half-sizes so that the whole space fits in u128
+        let half: u128 = ret.iter().map(|p| !p.mask / 2 + 1).sum();
+        assert_eq!(half, 1 << 127);
+        assert_eq!(Ipv6Pool::FULL, Ipv6Pool::from("::/0").unwrap());
+        assert!(Ipv6Pool::FULL.complement().is_empty());
+        for (i, a) in ret.iter().enumerate() {
+            for b in &ret[i + 1..] {
+                assert!(!a.contains_pool(b) && !b.contains_pool(a));
+            }
+        }
+    }
+    #[test]
+    fn ipv4_pool_print_prefix() {
+        let ips = Ipv4Pool::from("0.0.0.0/0").unwrap();
+        assert_eq!(format!("{}", ips), "0.0.0.0/0, next 0.0.0.0");
+        let ips = Ipv4Pool::from("128.0.0.0/1").unwrap();
+        assert_eq!(format!("{}", ips), "128.0.0.0/1, next 128.0.0.0");
+        let ips = Ipv4Pool::from("192.168.1.1/32").unwrap();
+        assert_eq!(format!("{}", ips), "192.168.1.1/32, next 192.168.1.1");
+    }
+    #[test]
+    fn ipv6_pool_print_prefix() {
+        let ips = Ipv6Pool::from("::/0").unwrap();
+        assert_eq!(format!("{}", ips), "::/0");
+        let ips = Ipv6Pool::from("2001:db8::1/128").unwrap();
+        assert_eq!(format!("{}", ips), "2001:db8::1/128");
+    }
+    #[test]
+    fn ipv4_pool_as_u32_range() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let range = ips.as_u32_range();
+        assert_eq!(*range.start(), u32::from(ips.network()));
+        assert_eq!(*range.end(), u32::from(ips.broadcast()));
+        assert!(range.contains(&u32::from(Ipv4Addr::new(192, 168, 1, 20))));
+        assert!(!range.contains(&u32::from(Ipv4Addr::new(192, 168, 2, 0))));
+    }
+    #[test]
+    fn ipv6_pool_as_u128_range() {
+        let ips = Ipv6Pool::from("2001:db8::/120").unwrap();
+        let range = ips.as_u128_range();
+        let last: Ipv6Addr = "2001:db8::ff".parse().unwrap();
+        assert_eq!(*range.start(), u128::from(ips.network()));
+        assert_eq!(*range.end(), u128::from(last));
+    }
+    #[test]
+    fn ipv6_pool_from_bracketed() {
+        let ips = Ipv6Pool::from("[2001:db8::]/32").unwrap();
+        let network: Ipv6Addr = "2001:db8::".parse().unwrap();
+        assert_eq!(ips.network(), network);
+        assert_eq!(ips.mask.count_ones(), 32);
+        assert!(Ipv6Pool::from("[2001:db8::/32").is_err());
+        assert!(Ipv6Pool::from("2001:db8::]/32").is_err());
+        assert!(Ipv6Pool::from("[[2001:db8::]]/32").is_err());
+    }
+    #[test]
+    fn ipv4_pool_allocate_sequential() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let ret = ips.allocate_sequential(26, 3).unwrap();
+        let ret: Vec<Ipv4Addr> = ret.iter().map(|p| p.network()).collect();
+        assert_eq!(
+            ret,
+            vec![
+                Ipv4Addr::new(192, 168, 1, 0),
+                Ipv4Addr::new(192, 168, 1, 64),
+                Ipv4Addr::new(192, 168, 1, 128),
+            ]
+        );
+        assert_eq!(ips.allocate_sequential(26, 4).unwrap().len(), 4);
+        assert!(ips.allocate_sequential(26, 5).is_err());
+        assert!(ips.allocate_sequential(23, 1).is_err());
+    }
+    #[test]
+    fn ipv4_pool_is_fully_covered_by() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let subnets = ips.allocate_sequential(26, 4).unwrap();
+        assert!(ips.is_fully_covered_by(&subnets));
+        let mut shuffled = subnets.clone();
+        shuffled.reverse();
+        assert!(ips.is_fully_covered_by(&shuffled));
+        // gap
+        assert!(!ips.is_fully_covered_by(&subnets[..3]));
+        let gap = vec![subnets[0], subnets[1], subnets[3]];
+        assert!(!ips.is_fully_covered_by(&gap));
+        // overlap
+        let mut overlap = subnets.clone();
+        overlap.push(Ipv4Pool::from("192.168.1.0/25").unwrap());
+        assert!(!ips.is_fully_covered_by(&overlap));
+        // out of bounds
+        let mut outside = subnets.clone();
+        outside.push(Ipv4Pool::from("192.168.2.0/26").unwrap());
+        assert!(!ips.is_fully_covered_by(&outside));
+        assert!(!ips.is_fully_covered_by(&[]));
+    }
+    #[test]
+    fn ipv4_bit_distance() {
+        let ipv4 = Ipv4::from("192.168.1.0").unwrap();
+        assert_eq!(ipv4.bit_distance(Ipv4Addr::new(192, 168, 1, 7)), 3);
+        assert_eq!(ipv4.bit_distance(Ipv4Addr::new(192, 168, 1, 0)), 0);
+        // largest_identical_prefix stops at the first differing bit
+        let ipv4_2 = Ipv4::from("192.168.1.7").unwrap();
+        assert_eq!(ipv4.largest_identical_prefix(ipv4_2), 29);
+    }
+    #[test]
+    fn ipv6_bit_distance() {
+        let ipv6 = Ipv6::from("2001:db8::").unwrap();
+        let other: Ipv6Addr = "2001:db8::1:0:7".parse().unwrap();
+        assert_eq!(ipv6.bit_distance(other), 4);
+    }
+    #[test]
+    fn ipv6_all_multicast() {
+        let ipv6 = Ipv6::from("::ffff:192.10.2.255").unwrap();
+        let set = ipv6.all_multicast();
+        println!("{:?}", set);
+        assert_eq!(set.node, ipv6.node_multicast());
+        assert_eq!(set.link, ipv6.link_multicast());
+        assert_eq!(set.site, ipv6.site_multicast());
+        let solicited_node: Ipv6Addr = "ff02::1:ff0a:2ff".parse().unwrap();
+        assert_eq!(set.solicited_node, solicited_node);
+        let site: Ipv6Addr = "ff05::1:ff0a:2ff".parse().unwrap();
+        assert_eq!(set.site, site);
+    }
+    #[test]
+    fn ipv4_floor_ceil_to_prefix() {
+        let ipv4 = Ipv4::from("192.168.1.130").unwrap();
+        assert_eq!(ipv4.floor_to_prefix(24), Ipv4Addr::new(192, 168, 1, 0));
+        assert_eq!(ipv4.ceil_to_prefix(24), Ipv4Addr::new(192, 168, 2, 0));
+        assert_eq!(ipv4.floor_to_prefix(32), Ipv4Addr::new(192, 168, 1, 130));
+        assert_eq!(ipv4.floor_to_prefix(0), Ipv4Addr::new(0, 0, 0, 0));
+        let aligned = Ipv4::from("192.168.1.0").unwrap();
+        assert_eq!(aligned.ceil_to_prefix(24), Ipv4Addr::new(192, 168, 1, 0));
+        let top = Ipv4::from("255.255.255.130").unwrap();
+        assert_eq!(top.ceil_to_prefix(24), Ipv4Addr::new(255, 255, 255, 255));
+    }
+    #[test]
+    fn ipv6_floor_ceil_to_prefix() {
+        let ipv6 = Ipv6::from("2001:db8::1:1").unwrap();
+        let floor: Ipv6Addr = "2001:db8::1:0".parse().unwrap();
+        let ceil: Ipv6Addr = "2001:db8::2:0".parse().unwrap();
+        assert_eq!(ipv6.floor_to_prefix(112), floor);
+        assert_eq!(ipv6.ceil_to_prefix(112), ceil);
+    }
+    #[test]
+    fn ipv4_pool_batched() {
+        let ips = Ipv4Pool::from("192.168.1.0/28").unwrap();
+        let batches: Vec<Vec<Ipv4Addr>> = ips.batched(5).collect();
+        let sizes: Vec<usize> = batches.iter().map(|b| b.len()).collect();
+        assert_eq!(sizes, vec![5, 5, 5, 1]);
+        assert_eq!(batches[0][0], Ipv4Addr::new(192, 168, 1, 0));
+        assert_eq!(batches[3][0], Ipv4Addr::new(192, 168, 1, 15));
+    }
+    #[test]
+    fn covers_addresses() {
+        let start = Ipv4Addr::new(10, 0, 0, 5);
+        let end = Ipv4Addr::new(10, 0, 0, 9);
+        let rules: Vec<Box<dyn CoversAddresses>> = vec![
+            Box::new(Ipv4Pool::from("192.168.1.0/24").unwrap()),
+            Box::new(CrossIpv4Pool::new(start, end).unwrap()),
+        ];
+        let counts: Vec<u64> = rules.iter().map(|r| r.address_count()).collect();
+        assert_eq!(counts, vec![256, 5]);
+        let ip = Ipv4Addr::new(192, 168, 1, 20);
+        assert!(rules[0].covers(ip));
+        assert!(!rules[1].covers(ip));
+        let ip = Ipv4Addr::new(10, 0, 0, 9);
+        assert!(!rules[0].covers(ip));
+        assert!(rules[1].covers(ip));
+        assert!(!rules.iter().any(|r| r.covers(Ipv4Addr::new(10, 0, 0, 10))));
+    }
+    #[test]
+    fn ipv4_pool_largest_free_block() {
+        let ips = Ipv4Pool::from("10.0.0.0/16").unwrap();
+        let allocated = [
+            Ipv4Pool::from("10.0.0.0/18").unwrap(),
+            Ipv4Pool::from("10.0.64.0/18").unwrap(),
+        ];
+        let free = ips.exclude_all(&allocated);
+        assert_eq!(free.len(), 1);
+        let block = ips.largest_free_block(&allocated).unwrap();
+        assert_eq!(block.network(), Ipv4Addr::new(10, 0, 128, 0));
+        assert_eq!(block.mask.count_ones(), 17);
+        let allocated = [
+            Ipv4Pool::from("10.0.0.0/24").unwrap(),
+            Ipv4Pool::from("10.0.200.0/24").unwrap(),
+        ];
+        let block = ips.largest_free_block(&allocated).unwrap();
+        assert_eq!(block.network(), Ipv4Addr::new(10, 0, 64, 0));
+        assert_eq!(block.mask.count_ones(), 18);
+        let allocated = [ips];
+        assert!(ips.largest_free_block(&allocated).is_none());
+    }
+    #[test]
+    fn ipv4_pool_from_urlencoded() {
+        let ips = Ipv4Pool::from("192.168.1.0%2F24").unwrap();
+        assert_eq!(ips.network(), Ipv4Addr::new(192, 168, 1, 0));
+        assert_eq!(ips.mask.count_ones(), 24);
+        let ips = Ipv4Pool::from("192.168.1.0%2f24").unwrap();
+        assert_eq!(ips.network(), Ipv4Addr::new(192, 168, 1, 0));
+        assert!(Ipv4Pool::from("192.168.1.0%2F24%2F8").is_err());
+        assert!(Ipv4Pool::from("192.168.1.0%2524").is_err());
+    }
+    #[test]
+    fn ipv4_pool_vlsm() {
+        let ips = Ipv4Pool::from("192.168.0.0/16").unwrap();
+        let requirements = [20, 100, 50];
+        let ret = ips.vlsm(&requirements).unwrap();
+        let ret_str: Vec<String> = ret
+            .iter()
+            .map(|p| format!("{}/{}", p.network(), p.mask.count_ones()))
+            .collect();
+        assert_eq!(
+            ret_str,
+            vec!["192.168.0.0/25", "192.168.0.128/26", "192.168.0.192/27"]
+        );
+        for (pool, hosts) in ret.iter().zip([100, 50, 20]) {
+            assert!(pool.size() - 2 >= hosts);
+            assert!(ips.contains_pool(pool));
+        }
+        for (i, a) in ret.iter().enumerate() {
+            for b in &ret[i + 1..] {
+                assert!(!a.contains_pool(b) && !b.contains_pool(a));
+            }
+        }
+        let small = Ipv4Pool::from("192.168.0.0/24").unwrap();
+        assert!(small.vlsm(&[200, 100]).is_err());
+        assert!(small.vlsm(&[255]).is_err());
+    }
+    #[test]
+    fn ipv6_multicast_low24() {
+        let unicast = Ipv6::from("::ffff:192.10.2.255").unwrap();
+        let solicited_node = Ipv6::new(unicast.solicited_node_multicast());
+        assert_eq!(solicited_node.multicast_low24(), 0x0a02ff);
+        assert_eq!(solicited_node.multicast_low24(), unicast.multicast_low24());
+    }
+    #[test]
+    fn ipv4_pool_usable_hosts() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        assert_eq!(ips.usable_hosts(), 254);
+        let ips = Ipv4Pool::from("192.168.1.0/31").unwrap();
+        assert_eq!(ips.usable_hosts(), 2);
+        let ips = Ipv4Pool::from("192.168.1.0/32").unwrap();
+        assert_eq!(ips.usable_hosts(), 1);
+    }
+    #[test]
+    fn total_usable_hosts() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let pools = [
+            Ipv4Pool::from("192.168.1.0/25").unwrap(),
+            ips,
+            Ipv4Pool::from("192.168.1.128/25").unwrap(),
+        ];
+        assert_eq!(total_usable_hosts_ipv4(&pools), ips.usable_hosts());
+        let pools = [
+            Ipv4Pool::from("192.168.1.0/25").unwrap(),
+            Ipv4Pool::from("192.168.1.128/25").unwrap(),
+        ];
+        assert_eq!(total_usable_hosts_ipv4(&pools), 254);
+    }
+    #[test]
+    fn ipv4_from_u32() {
+        let ipv4: Ipv4 = 3232235777u32.into();
+        assert_eq!(ipv4.to_string(), "192.168.1.1");
+        let ipv4 = Ipv4::from_u32(3232235777);
+        assert_eq!(ipv4.to_string(), "192.168.1.1");
+        let ipv6: Ipv6 = 281473903624959u128.into();
+        assert_eq!(ipv6.to_string(), "::ffff:192.10.2.255");
+    }
+    #[test]
+    fn cross_ipv4_pool_spans_multiple() {
+        let start = Ipv4Addr::new(192, 168, 1, 1);
+        let end = Ipv4Addr::new(192, 168, 1, 254);
+        let ips = CrossIpv4Pool::new(start, end).unwrap();
+        assert!(!ips.spans_multiple(24));
+        assert!(ips.spans_multiple(25));
+        let end = Ipv4Addr::new(192, 168, 2, 1);
+        let ips = CrossIpv4Pool::new(start, end).unwrap();
+        assert!(ips.spans_multiple(24));
+        assert!(!ips.spans_multiple(0));
+    }
+    #[test]
+    fn ipv4_pool_iter_excluding_range() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let start = Ipv4Addr::new(192, 168, 1, 100);
+        let end = Ipv4Addr::new(192, 168, 1, 199);
+        let skip = CrossIpv4Pool::new(start, end).unwrap();
+        let ret: Vec<Ipv4Addr> = ips.iter_excluding_range(&skip).collect();
+        assert_eq!(ret.len() as u64, ips.size() as u64 - skip.address_count());
+        assert_eq!(ret[99], Ipv4Addr::new(192, 168, 1, 99));
+        assert_eq!(ret[100], Ipv4Addr::new(192, 168, 1, 200));
+        assert!(!ret.iter().any(|ip| skip.covers(*ip)));
+        // a range straddling the pool only skips its part inside the pool
+        let start = Ipv4Addr::new(192, 168, 0, 0);
+        let end = Ipv4Addr::new(192, 168, 1, 9);
+        let skip = CrossIpv4Pool::new(start, end).unwrap();
+        assert_eq!(ips.iter_excluding_range(&skip).count(), 246);
+        let start = Ipv4Addr::new(10, 0, 0, 0);
+        let end = Ipv4Addr::new(10, 0, 0, 9);
+        let skip = CrossIpv4Pool::new(start, end).unwrap();
+        assert_eq!(ips.iter_excluding_range(&skip).count(), 256);
+    }
+    #[test]
+    fn ipv4_pool_from_addr() {
+        let ip = Ipv4Addr::new(192, 168, 1, 7);
+        let ips: Ipv4Pool = ip.into();
+        assert_eq!(ips.size(), 1);
+        assert_eq!(ips.network(), ip);
+        assert_eq!(ips.broadcast(), ip);
+        assert!(ips.contain(ip));
+        assert!(!ips.contain(Ipv4Addr::new(192, 168, 1, 8)));
+    }
+    #[test]
+    fn ipv6_pool_from_addr() {
+        let ip: Ipv6Addr = "2001:db8::7".parse().unwrap();
+        let ips: Ipv6Pool = ip.into();
+        assert_eq!(ips.to_vec(), vec![ip]);
+        assert!(ips.contain(ip));
+    }
+    #[test]
+    fn ipv4_pool_diff() {
+        let old = Ipv4Pool::from("192.168.0.0/24").unwrap();
+        let new = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let (added, removed) = old.diff(&new);
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].network(), new.network());
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].network(), old.network());
+        let (added, removed) = old.diff(&old);
+        assert!(added.is_empty());
+        assert!(removed.is_empty());
+    }
+    #[test]
+    fn ipv4_pool_iter_gray() {
+        let ips = Ipv4Pool::from("192.168.1.0/26").unwrap();
+        let gray: Vec<Ipv4Addr> = ips.iter_gray().collect();
+        assert_eq!(gray.len(), ips.to_vec().len());
+        let gray_set: BTreeSet<Ipv4Addr> = gray.iter().copied().collect();
+        let all_set: BTreeSet<Ipv4Addr> = ips.to_vec().into_iter().collect();
+        assert_eq!(gray_set, all_set);
+        for pair in gray.windows(2) {
+            let a: u32 = pair[0].into();
+            let b: u32 = pair[1].into();
+            assert_eq!((a ^ b).count_ones(), 1);
+        }
+    }
+    #[test]
+    fn ipv4_pool_position_of() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let ret = ips.position_of(Ipv4Addr::new(192, 168, 0, 255));
+        assert_eq!(ret, Ordering::Less);
+        let ret = ips.position_of(Ipv4Addr::new(192, 168, 1, 0));
+        assert_eq!(ret, Ordering::Equal);
+        let ret = ips.position_of(Ipv4Addr::new(192, 168, 1, 255));
+        assert_eq!(ret, Ordering::Equal);
+        let ret = ips.position_of(Ipv4Addr::new(192, 168, 2, 0));
+        assert_eq!(ret, Ordering::Greater);
+    }
+    #[test]
+    fn ipv4_pool_len_matches_to_vec() {
+        for prefix_len in [16, 24, 30, 31, 32] {
+            let ips = Ipv4Pool::new(Ipv4Addr::new(10, 1, 2, 3), prefix_len).unwrap();
+            assert_eq!(ips.to_vec().len(), ips.len());
+            assert_eq!(ips.max_vec_len(), ips.len());
+        }
+        let ips = Ipv4Pool::from("10.1.2.3/32").unwrap();
+        assert_eq!(ips.len(), 1);
+    }
+    #[test]
+    fn ipv6_pool_len_matches_to_vec() {
+        for prefix_len in [112, 120, 127, 128] {
+            let ips = Ipv6Pool::new("2001:db8::1".parse().unwrap(), prefix_len).unwrap();
+            assert_eq!(ips.to_vec().len(), ips.len());
+        }
+        let ips = Ipv6Pool::from("2001:db8::/32").unwrap();
+        assert_eq!(ips.len(), usize::MAX);
+    }
+    #[test]
+    fn ipv4_pool_is_gateway() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let first = Ipv4Addr::new(192, 168, 1, 1);
+        let last = Ipv4Addr::new(192, 168, 1, 254);
+        assert!(ips.is_gateway(first, GatewayConvention::First));
+        assert!(!ips.is_gateway(last, GatewayConvention::First));
+        assert!(ips.is_gateway(last, GatewayConvention::Last));
+        assert!(!ips.is_gateway(first, GatewayConvention::Last));
+        for convention in [GatewayConvention::First, GatewayConvention::Last] {
+            assert!(!ips.is_gateway(ips.network(), convention));
+            assert!(!ips.is_gateway(ips.broadcast(), convention));
+        }
+    }
+    #[test]
+    fn ipv4_pool_single_host() {
+        let ip = Ipv4Addr::new(192, 168, 1, 7);
+        let ips = Ipv4Pool::new(ip, 32).unwrap();
+        assert!(ips.is_single_host());
+        assert_eq!(ips.collect::<Vec<Ipv4Addr>>(), vec![ip]);
+        assert_eq!(ips.network(), ip);
+        assert_eq!(ips.broadcast(), ip);
+        assert_eq!(ips.network(), ips.broadcast());
+        assert_eq!(ips.len(), 1);
+        assert!(ips.contain(ip));
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        assert!(!ips.is_single_host());
+        assert_eq!(ips.count(), 256);
+    }
+    #[test]
+    fn ipv6_pool_single_host() {
+        let ip: Ipv6Addr = "2001:db8::7".parse().unwrap();
+        let ips = Ipv6Pool::new(ip, 128).unwrap();
+        assert!(ips.is_single_host());
+        assert_eq!(ips.collect::<Vec<Ipv6Addr>>(), vec![ip]);
+        assert_eq!(ips.network(), ip);
+        assert_eq!(ips.len(), 1);
+        assert!(ips.contain(ip));
+    }
+    #[test]
+    fn normalize_pools() {
+        let pools = [
+            Ipv4Pool::from("192.168.3.0/24").unwrap(),
+            Ipv4Pool::from("192.168.1.0/24").unwrap(),
+            Ipv4Pool::from("192.168.0.0/24").unwrap(),
+            Ipv4Pool::from("192.168.2.0/25").unwrap(),
+            Ipv4Pool::from("192.168.2.128/25").unwrap(),
+            Ipv4Pool::from("192.168.2.0/26").unwrap(),
+        ];
+        let ret = normalize_ipv4(&pools);
+        assert_eq!(ret.len(), 1);
+        assert_eq!(ret[0].to_string(), "192.168.0.0/22, next 192.168.0.0");
+        let pools = [
+            Ipv4Pool::from("192.168.1.0/24").unwrap(),
+            Ipv4Pool::from("192.168.2.0/24").unwrap(),
+        ];
+        assert_eq!(normalize_ipv4(&pools).len(), 2);
+    }
+    #[test]
+    fn normalize_mixed_pools() {
+        let pools = [
+            IpPool::V6(Ipv6Pool::from("2001:db8:8000::/33").unwrap()),
+            IpPool::V4(Ipv4Pool::from("192.168.1.0/24").unwrap()),
+            IpPool::V6(Ipv6Pool::from("2001:db8::/33").unwrap()),
+            IpPool::V4(Ipv4Pool::from("192.168.0.0/24").unwrap()),
+        ];
+        let ret = normalize_mixed(&pools);
+        assert_eq!(ret.len(), 2);
+        match ret[0] {
+            IpPool::V4(p) => assert_eq!(p.to_string(), "192.168.0.0/23, next 192.168.0.0"),
+            IpPool::V6(_) => panic!("expected the ipv4 pool first"),
+        }
+        match ret[1] {
+            IpPool::V4(_) => panic!("expected the ipv6 pool last"),
+            IpPool::V6(p) => assert_eq!(p.to_string(), "2001:db8::/32"),
+        }
+    }
+    #[test]
+    fn ipv4_pool_prefix_for_subnet_count() {
+        let ips = Ipv4Pool::from("10.0.0.0/16").unwrap();
+        assert_eq!(ips.prefix_for_subnet_count(10).unwrap(), 20);
+        assert_eq!(ips.prefix_for_subnet_count(16).unwrap(), 20);
+        assert_eq!(ips.prefix_for_subnet_count(17).unwrap(), 21);
+        assert_eq!(ips.prefix_for_subnet_count(1).unwrap(), 16);
+        assert_eq!(ips.prefix_for_subnet_count(65536).unwrap(), 32);
+        assert!(ips.prefix_for_subnet_count(65537).is_err());
+    }
+    #[test]
+    fn ipv4_pool_iter_with_ptr() {
+        let ips = Ipv4Pool::from("192.168.1.0/30").unwrap();
+        let ret: Vec<(Ipv4Addr, String)> = ips.iter_with_ptr().collect();
+        assert_eq!(ret.len(), 4);
+        assert_eq!(ret[0].0, Ipv4Addr::new(192, 168, 1, 0));
+        assert_eq!(ret[0].1, "0.1.168.192.in-addr.arpa");
+        assert_eq!(ret[3].1, "3.1.168.192.in-addr.arpa");
+    }
+    #[test]
+    fn ipv6_pool_iter_with_ptr() {
+        let ips = Ipv6Pool::from("2001:db8::/127").unwrap();
+        let ret: Vec<(Ipv6Addr, String)> = ips.iter_with_ptr().collect();
+        assert_eq!(ret.len(), 2);
+        assert_eq!(ret[0].0, ips.network());
+        assert_eq!(
+            ret[0].1,
+            "0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa"
+        );
+        assert!(ret[1].1.starts_with("1.0.0.0."));
+    }
+    #[test]
+    fn ipv4_pool_reverse_zones_24() {
+        let ips = Ipv4Pool::from("10.0.0.0/22").unwrap();
+        let zones = ips.reverse_zones_24();
+        assert_eq!(
+            zones,
+            vec![
+                "0.0.10.in-addr.arpa",
+                "1.0.10.in-addr.arpa",
+                "2.0.10.in-addr.arpa",
+                "3.0.10.in-addr.arpa",
+            ]
+        );
+        let ips = Ipv4Pool::from("192.168.1.64/26").unwrap();
+        assert_eq!(ips.reverse_zones_24(), vec!["1.168.192.in-addr.arpa"]);
+    }
+    #[test]
+    fn ipv4_from_decimal() {
+        assert_eq!(
+            Ipv4::from_decimal(3232235777),
+            Ipv4Addr::new(192, 168, 1, 1)
+        );
+        let ipv4 = Ipv4::from("3232235777").unwrap();
+        assert_eq!(ipv4.to_std(), Ipv4Addr::new(192, 168, 1, 1));
+        let ipv4 = Ipv4::from("10").unwrap();
+        assert_eq!(ipv4.to_std(), Ipv4Addr::new(0, 0, 0, 10));
+        assert!(Ipv4::from("4294967296").is_err());
+        assert!(Ipv4::from("10.1").is_err());
+        assert!(Ipv4::from("").is_err());
+    }
+    #[test]
+    fn ipv4_pool_is_disjoint() {
+        let ips_1 = Ipv4Pool::from("192.168.0.0/24").unwrap();
+        let ips_2 = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let ips_3 = Ipv4Pool::from("192.168.0.0/16").unwrap();
+        assert!(ips_1.is_disjoint(&ips_2));
+        assert!(!ips_1.overlaps(&ips_2));
+        assert!(!ips_1.is_disjoint(&ips_3));
+        assert!(ips_3.overlaps(&ips_1));
+    }
+    #[test]
+    fn ipv6_pool_is_disjoint() {
+        let ips_1 = Ipv6Pool::from("2001:db8::/33").unwrap();
+        let ips_2 = Ipv6Pool::from("2001:db8:8000::/33").unwrap();
+        let ips_3 = Ipv6Pool::from("2001:db8::/32").unwrap();
+        assert!(ips_1.is_disjoint(&ips_2));
+        assert!(!ips_2.is_disjoint(&ips_3));
+    }
+    #[test]
+    fn ipv4_pool_resize() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let supernet = ips.resize(16).unwrap();
+        assert_eq!(supernet.network(), Ipv4Addr::new(192, 168, 0, 0));
+        assert_eq!(supernet.mask.count_ones(), 16);
+        assert!(supernet.contains_pool(&ips));
+        let subnet = ips.resize(28).unwrap();
+        assert_eq!(subnet.network(), Ipv4Addr::new(192, 168, 1, 0));
+        assert_eq!(subnet.broadcast(), Ipv4Addr::new(192, 168, 1, 15));
+        assert!(ips.contains_pool(&subnet));
+        let ips = Ipv4Pool::from("192.168.1.128/25").unwrap();
+        assert_eq!(
+            ips.resize(22).unwrap().network(),
+            Ipv4Addr::new(192, 168, 0, 0)
+        );
+        assert!(ips.resize(33).is_err());
+    }
+    #[test]
+    fn ipv4_pool_iter_without_network() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let ret: Vec<Ipv4Addr> = ips.iter_without_network().collect();
+        assert_eq!(ret.len(), ips.len() - 1);
+        assert_eq!(ret[0], Ipv4Addr::new(192, 168, 1, 1));
+        assert_eq!(ret[ret.len() - 1], ips.broadcast());
+        let ips = Ipv4Pool::from("192.168.1.0/31").unwrap();
+        assert_eq!(ips.iter_without_network().count(), 2);
+        let ips = Ipv4Pool::from("192.168.1.0/32").unwrap();
+        assert_eq!(ips.iter_without_network().count(), 1);
+    }
+    #[test]
+    fn ipv4_pool_union_addresses() {
+        let a = Ipv4Pool::from("192.168.1.128/25").unwrap();
+        let b = Ipv4Pool::from("192.168.1.0/25").unwrap();
+        let ret = a.union_addresses(&b);
+        assert_eq!(ret, Ipv4Pool::from("192.168.1.0/24").unwrap().to_vec());
+        let c = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let ret = a.union_addresses(&c);
+        assert_eq!(ret.len(), 256);
+        assert!(ret.windows(2).all(|w| w[0] < w[1]));
+    }
+    #[test]
+    fn ipv4_pool_distinguishing_bits() {
+        let a = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let b = Ipv4Pool::from("192.168.3.0/24").unwrap();
+        assert_eq!(a.distinguishing_bits(&b), 23);
+        let c = Ipv4Pool::from("192.168.1.0/26").unwrap();
+        assert_eq!(a.distinguishing_bits(&c), 26);
+    }
+    #[test]
+    fn cross_ipv4_pool_snap_to_cidr() {
+        let start = Ipv4Addr::new(192, 168, 1, 10);
+        let end = Ipv4Addr::new(192, 168, 3, 20);
+        let ips = CrossIpv4Pool::new(start, end).unwrap();
+        let inner = ips.shrink_to_cidr().unwrap();
+        assert_eq!(
+            inner.to_string(),
+            Ipv4Pool::from("192.168.2.0/24").unwrap().to_string()
         );
-        let node = Ipv6::new(node);
-        let mask = Ipv6Addr::new(
-            0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x00FF, 0xFFFF,
+        let outer = ips.grow_to_cidr();
+        assert_eq!(
+            outer.to_string(),
+            Ipv4Pool::from("192.168.0.0/22").unwrap().to_string()
         );
-        let mask = Ipv6::new(mask);
-        (node.addr + (mask.addr & self.addr)).into()
-    }
-    /// Returns the link local scope multicast address of this `Ipv6`.
-    pub fn link_multicast(&self) -> Ipv6Addr {
-        let link = Ipv6Addr::new(
-            0xFF02, 0x0000, 0x0000, 0x0000, 0x0000, 0x0001, 0xFF00, 0x0000,
+        let start = Ipv4Addr::new(192, 168, 1, 7);
+        let end = Ipv4Addr::new(192, 168, 1, 8);
+        let ips = CrossIpv4Pool::new(start, end).unwrap();
+        assert_eq!(
+            ips.shrink_to_cidr().unwrap().to_string(),
+            Ipv4Pool::from("192.168.1.7/32").unwrap().to_string()
         );
-        let link = Ipv6::new(link);
-        let mask = Ipv6Addr::new(
-            0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x00FF, 0xFFFF,
+        assert_eq!(
+            ips.grow_to_cidr().to_string(),
+            Ipv4Pool::from("192.168.1.0/28").unwrap().to_string()
         );
-        let mask = Ipv6::new(mask);
-        (link.addr + (mask.addr & self.addr)).into()
     }
-    /// Returns the site local scope multicast address of this `Ipv6`.
-    pub fn site_multicast(&self) -> Ipv6Addr {
-        let site = Ipv6Addr::new(
-            0xFF05, 0x0000, 0x0000, 0x0000, 0x0000, 0x0001, 0xFF00, 0x0000,
+    #[test]
+    fn ipv6_pool_subnets_preview() {
+        let ips = Ipv6Pool::from("2001:db8:0:100::/56").unwrap();
+        let preview = ips.subnets_preview(64, 5).unwrap();
+        assert_eq!(preview.len(), 5);
+        assert_eq!(
+            preview[0].network(),
+            "2001:db8:0:100::".parse::<Ipv6Addr>().unwrap()
         );
-        let site = Ipv6::new(site);
-        let mask = Ipv6Addr::new(
-            0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x0000, 0x00FF, 0xFFFF,
+        assert_eq!(
+            preview[4].network(),
+            "2001:db8:0:104::".parse::<Ipv6Addr>().unwrap()
         );
-        let mask = Ipv6::new(mask);
-        (site.addr + (mask.addr & self.addr)).into()
+        assert_eq!(ips.subnets(64).unwrap().count(), 256);
+        assert!(ips.subnets_preview(48, 5).is_err());
     }
-    /// Returns the standard IPv4 address.
-    pub fn to_std(&self) -> Ipv6Addr {
-        self.addr.into()
+    #[test]
+    fn ipv4_pool_contains_range() {
+        let ips = Ipv4Pool::from("192.168.0.0/16").unwrap();
+        let start = Ipv4Addr::new(192, 168, 10, 1);
+        let end = Ipv4Addr::new(192, 168, 20, 254);
+        let range = CrossIpv4Pool::new(start, end).unwrap();
+        assert!(ips.contains_range(&range));
+        let start = Ipv4Addr::new(192, 168, 255, 1);
+        let end = Ipv4Addr::new(192, 169, 0, 10);
+        let range = CrossIpv4Pool::new(start, end).unwrap();
+        assert!(!ips.contains_range(&range));
     }
-    pub fn max_identical_prefix(&self, target: Ipv6) -> u128 {
-        let a = self.addr;
-        let b = target.addr;
-        let mut mask = 1;
-        for _ in 0..(IPV6_LEN - 1) {
-            mask <<= 1;
-        }
-        let mut count = 0;
-        for _ in 0..IPV6_LEN {
-            if a & mask != b & mask {
-                break;
-            }
-            count += 1;
-            mask >>= 1;
-        }
-        count - 1
+    #[test]
+    fn pool_prefix_alignment() {
+        assert!(Ipv4Pool::from("192.168.1.0/24").unwrap().is_octet_aligned());
+        assert!(!Ipv4Pool::from("192.168.1.0/26").unwrap().is_octet_aligned());
+        assert!(Ipv6Pool::from("2001:db8::/52").unwrap().is_nibble_aligned());
+        assert!(!Ipv6Pool::from("2001:db8::/50").unwrap().is_nibble_aligned());
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    /* cross ipv4 pool */
     #[test]
-    fn cross_ipv4_pool_print() {
-        let start = Ipv4Addr::new(192, 168, 1, 1);
-        let end = Ipv4Addr::new(192, 168, 3, 254);
-        let ips = CrossIpv4Pool::new(start, end).unwrap();
-        for i in ips {
-            println!("{:?}", i);
+    fn ipv4_pool_addresses_progress() {
+        let mut ips = Ipv4Pool::from("192.168.1.0/28").unwrap();
+        assert_eq!(ips.addresses_consumed(), 0);
+        assert_eq!(ips.addresses_remaining(), 16);
+        for _ in 0..5 {
+            ips.next();
         }
+        assert_eq!(ips.addresses_consumed(), 5);
+        assert_eq!(ips.addresses_remaining(), 11);
+        ips.next_back();
+        ips.next_back();
+        ips.next();
+        assert_eq!(ips.addresses_consumed(), 8);
+        assert_eq!(ips.addresses_remaining(), 8);
+        let size = ips.size() as u64;
+        assert_eq!(ips.addresses_consumed() + ips.addresses_remaining(), size);
+        ips.by_ref().count();
+        assert_eq!(ips.addresses_remaining(), 0);
+        assert_eq!(ips.addresses_consumed(), size);
     }
-    /* ipv4 test */
     #[test]
-    fn ipv4_pool_print() {
-        let test_str = "192.168.1.0/24";
-        let ipv4_pool = Ipv4Pool::from(test_str).unwrap();
-        let ipv4_pool_str = format!("{}", ipv4_pool);
-        println!("{}", ipv4_pool_str);
+    fn ipv4_pool_for_host_count() {
+        let network = Ipv4Addr::new(192, 168, 1, 0);
+        let ips = Ipv4Pool::for_host_count(network, 200).unwrap();
+        assert_eq!(ips.mask.count_ones(), 24);
+        let ips = Ipv4Pool::for_host_count(network, 254).unwrap();
+        assert_eq!(ips.mask.count_ones(), 24);
+        assert!(Ipv4Pool::for_host_count(network, 255).is_err());
+        let ips = Ipv4Pool::for_host_count(Ipv4Addr::new(192, 168, 0, 0), 255).unwrap();
+        assert_eq!(ips.mask.count_ones(), 23);
+        let ips = Ipv4Pool::for_host_count(network, 2).unwrap();
+        assert_eq!(ips.mask.count_ones(), 31);
+        let network = Ipv4Addr::new(192, 168, 1, 128);
+        assert!(Ipv4Pool::for_host_count(network, 200).is_err());
+        assert!(Ipv4Pool::for_host_count(network, u32::MAX).is_err());
     }
     #[test]
-    fn ipv4_print() {
-        let test_str = "192.168.1.1";
-        let ipv4 = Ipv4::from(test_str).unwrap();
-        let ipv4_str = format!("{}", ipv4);
-        assert_eq!(ipv4_str, test_str);
+    fn cross_ipv4_pool_symmetric_difference() {
+        let a = CrossIpv4Pool::new(
+            Ipv4Addr::new(192, 168, 1, 1),
+            Ipv4Addr::new(192, 168, 1, 100),
+        )
+        .unwrap();
+        let b = CrossIpv4Pool::new(
+            Ipv4Addr::new(192, 168, 1, 50),
+            Ipv4Addr::new(192, 168, 1, 200),
+        )
+        .unwrap();
+        let ret = a.symmetric_difference(&b);
+        assert_eq!(ret.len(), 2);
+        assert_eq!(ret[0].start, u32::from(Ipv4Addr::new(192, 168, 1, 1)));
+        assert_eq!(ret[0].end, u32::from(Ipv4Addr::new(192, 168, 1, 49)));
+        assert_eq!(ret[1].start, u32::from(Ipv4Addr::new(192, 168, 1, 101)));
+        assert_eq!(ret[1].end, u32::from(Ipv4Addr::new(192, 168, 1, 200)));
+        assert!(a.symmetric_difference(&a).is_empty());
+        let c = CrossIpv4Pool::new(
+            Ipv4Addr::new(192, 168, 1, 101),
+            Ipv4Addr::new(192, 168, 1, 150),
+        )
+        .unwrap();
+        let ret = c.symmetric_difference(&a);
+        assert_eq!(ret.len(), 1);
+        assert_eq!(ret[0].start, u32::from(Ipv4Addr::new(192, 168, 1, 1)));
+        assert_eq!(ret[0].end, u32::from(Ipv4Addr::new(192, 168, 1, 150)));
     }
     #[test]
-    fn ipv4_iter() {
-        let ipv4 = Ipv4::from("192.168.1.1").unwrap();
-        for i in ipv4.iter(24).unwrap() {
-            println!("{:?}", i);
-        }
-        assert_eq!(1, 1);
+    fn pool_to_json() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        assert_eq!(
+            ips.to_json(),
+            r#"{"network":"192.168.1.0","prefix":24,"netmask":"255.255.255.0","broadcast":"192.168.1.255"}"#
+        );
+        let ips = Ipv6Pool::from("2001:db8::/32").unwrap();
+        assert_eq!(
+            ips.to_json(),
+            r#"{"network":"2001:db8::","prefix":32,"netmask":"ffff:ffff::"}"#
+        );
     }
     #[test]
-    fn ipv6_iter() {
-        let ipv6 = Ipv6::from("::ffff:192.10.2.255").unwrap();
-        for i in ipv6.iter(124).unwrap() {
-            println!("{:?}", i);
-        }
-        assert_eq!(1, 1);
+    fn ipv4_pool_is_assignable_subnet() {
+        assert!(Ipv4Pool::from("192.168.1.0/30")
+            .unwrap()
+            .is_assignable_subnet());
+        assert!(!Ipv4Pool::from("192.168.1.0/31")
+            .unwrap()
+            .is_assignable_subnet());
+        assert!(!Ipv4Pool::from("192.168.1.0/32")
+            .unwrap()
+            .is_assignable_subnet());
     }
     #[test]
-    fn ipv4() {
-        let ipv4 = Ipv4::from("192.168.1.1").unwrap();
-        println!("{:8b}", ipv4.addr);
-        assert_eq!(ipv4.addr, 3232235777);
+    fn pool_neighbors() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let (prev, next) = ips.neighbors(Ipv4Addr::new(192, 168, 1, 10));
+        assert_eq!(prev, Some(Ipv4Addr::new(192, 168, 1, 9)));
+        assert_eq!(next, Some(Ipv4Addr::new(192, 168, 1, 11)));
+        let (prev, next) = ips.neighbors(ips.network());
+        assert_eq!(prev, None);
+        assert_eq!(next, Some(Ipv4Addr::new(192, 168, 1, 1)));
+        let (prev, next) = ips.neighbors(ips.broadcast());
+        assert_eq!(prev, Some(Ipv4Addr::new(192, 168, 1, 254)));
+        assert_eq!(next, None);
+        let ips = Ipv6Pool::from("2001:db8::/126").unwrap();
+        let (prev, next) = ips.neighbors("2001:db8::3".parse().unwrap());
+        assert_eq!(prev, Some("2001:db8::2".parse().unwrap()));
+        assert_eq!(next, None);
     }
-    /* ipv6 test */
     #[test]
-    fn ipv6() {
-        let ipv6 = Ipv6::from("::ffff:192.10.2.255").unwrap();
-        println!("{:?}", ipv6);
-        assert_eq!(ipv6.addr, 281473903624959);
+    fn common_supernet() {
+        let ips = common_supernet_str("192.168.0.0/24", "192.168.1.0/24").unwrap();
+        assert_eq!(ips.network(), Ipv4Addr::new(192, 168, 0, 0));
+        assert_eq!(ips.mask.count_ones(), 23);
+        let ips = common_supernet_str("192.168.1.0/24", "192.168.2.0/24").unwrap();
+        assert_eq!(ips.mask.count_ones(), 22);
+        assert!(common_supernet_str("192.168.1.0/24", "192.168.2.0").is_err());
     }
     #[test]
-    fn ipv6_node() {
-        // let a: u8 = 0b1100;
-        // let b: u8 = 0b0011;
-        // println!("{}", a + b);
-        let ipv6 = Ipv6::from("::ffff:192.10.2.255").unwrap();
-        let ipv6_2: Ipv6Addr = "ff01::1:ff0a:2ff".parse().unwrap();
-        println!("{:?}", ipv6.node_multicast());
-        assert_eq!(ipv6.node_multicast(), ipv6_2);
+    fn ipv4_pool_iter_from() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let ret: Vec<Ipv4Addr> = ips.iter_from(100).collect();
+        assert_eq!(ret[0], Ipv4Addr::new(192, 168, 1, 100));
+        assert_eq!(ret.len(), 156);
+        assert_eq!(ips.iter_from(255).count(), 1);
+        assert_eq!(ips.iter_from(256).count(), 0);
+        assert_eq!(ips.iter_from(u32::MAX).count(), 0);
     }
     #[test]
-    fn ipv6_link() {
-        let ipv6 = Ipv6::from("::ffff:192.10.2.255").unwrap();
-        let ipv6_2: Ipv6Addr = "ff02::1:ff0a:2ff".parse().unwrap();
-        println!("{:?}", ipv6.link_multicast());
-        assert_eq!(ipv6.link_multicast(), ipv6_2);
+    fn ipv4_pool_to_binary_with_boundary() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let ret = ips.to_binary_with_boundary();
+        assert_eq!(ret, "11000000.10101000.00000001.|00000000");
+        let ips = Ipv4Pool::from("192.168.1.64/26").unwrap();
+        let ret = ips.to_binary_with_boundary();
+        assert_eq!(ret, "11000000.10101000.00000001.01|000000");
+        let ips = Ipv4Pool::from("192.168.1.1/32").unwrap();
+        let ret = ips.to_binary_with_boundary();
+        assert_eq!(ret, "11000000.10101000.00000001.00000001|");
     }
-    /* ipv4 pool test */
     #[test]
-    fn ipv4_pool() {
+    fn pool_overlaps_any() {
+        let pool = Ipv4Pool::from("192.168.1.128/25").unwrap();
+        let others = [
+            Ipv4Pool::from("10.0.0.0/8").unwrap(),
+            Ipv4Pool::from("192.168.1.0/25").unwrap(),
+            Ipv4Pool::from("192.168.0.0/16").unwrap(),
+            Ipv4Pool::from("192.168.1.0/24").unwrap(),
+        ];
+        assert_eq!(overlaps_any_ipv4(&pool, &others), Some(2));
+        assert_eq!(overlaps_any_ipv4(&pool, &others[..2]), None);
+        let pool = Ipv6Pool::from("2001:db8::/48").unwrap();
+        let others = [
+            Ipv6Pool::from("2001:db9::/32").unwrap(),
+            Ipv6Pool::from("2001:db8::/32").unwrap(),
+        ];
+        assert_eq!(overlaps_any_ipv6(&pool, &others), Some(1));
+    }
+    #[test]
+    fn ipv4_pool_utilization() {
+        let ips = Ipv4Pool::from("10.0.0.0/22").unwrap();
+        let allocated = [
+            Ipv4Pool::from("10.0.0.0/24").unwrap(),
+            Ipv4Pool::from("10.0.2.0/24").unwrap(),
+            Ipv4Pool::from("10.0.2.0/25").unwrap(),
+        ];
+        assert_eq!(ips.utilization(&allocated), 0.5);
+        assert_eq!(ips.utilization(&[]), 0.0);
+        let allocated = [Ipv4Pool::from("10.0.0.0/8").unwrap()];
+        assert_eq!(ips.utilization(&allocated), 1.0);
+    }
+    #[test]
+    fn single_addr_from_tuple() {
+        let ipv4: Ipv4 = (192, 168, 1, 1).into();
+        assert_eq!(ipv4.addr, Ipv4::new(Ipv4Addr::new(192, 168, 1, 1)).addr);
+        let ipv6: Ipv6 = (0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).into();
+        let expected = Ipv6::new(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+        assert_eq!(ipv6.addr, expected.addr);
+    }
+    #[test]
+    fn aggregate_ipv4_stats() {
+        let pools = [
+            Ipv4Pool::from("192.168.3.0/24").unwrap(),
+            Ipv4Pool::from("192.168.0.0/24").unwrap(),
+            Ipv4Pool::from("192.168.2.0/24").unwrap(),
+            Ipv4Pool::from("192.168.1.0/24").unwrap(),
+        ];
+        let (ret, eliminated) = aggregate_ipv4_with_stats(&pools);
+        assert_eq!(ret.len(), 1);
+        assert_eq!(ret[0].mask.count_ones(), 22);
+        assert_eq!(eliminated, 3);
+        let pools = [
+            Ipv4Pool::from("10.0.0.0/8").unwrap(),
+            Ipv4Pool::from("10.1.0.0/16").unwrap(),
+            Ipv4Pool::from("172.16.0.0/12").unwrap(),
+        ];
+        let (ret, eliminated) = aggregate_ipv4_with_stats(&pools);
+        assert_eq!(ret.len(), 2);
+        assert_eq!(eliminated, 1);
+    }
+    #[test]
+    fn ipv6_pool_is_ipv4_mapped() {
+        let ips = Ipv6Pool::from("::ffff:192.10.2.0/120").unwrap();
+        assert!(ips.is_ipv4_mapped());
+        let ips = Ipv6Pool::from("2001:db8::/32").unwrap();
+        assert!(!ips.is_ipv4_mapped());
+        let ips = Ipv6Pool::from("::/64").unwrap();
+        assert!(!ips.is_ipv4_mapped());
+        let mapped = Ipv4Pool::from("192.10.2.0/24").unwrap().to_ipv4_mapped();
+        assert!(mapped.is_ipv4_mapped());
+        assert_eq!(
+            mapped.network(),
+            "::ffff:192.10.2.0".parse::<Ipv6Addr>().unwrap()
+        );
+        assert_eq!(mapped.mask.count_ones(), 120);
+    }
+    #[test]
+    fn ipv4_pool_route_forms() {
+        let ips = Ipv4Pool::from("192.168.1.7/24").unwrap();
+        assert_eq!(ips.to_ip_route_dst(), "192.168.1.0/24");
+        assert_eq!(ips.to_ifconfig_form(), "192.168.1.0 netmask 255.255.255.0");
+        let ips = Ipv4Pool::from("10.0.0.0/12").unwrap();
+        assert_eq!(ips.to_ip_route_dst(), "10.0.0.0/12");
+        assert_eq!(ips.to_ifconfig_form(), "10.0.0.0 netmask 255.240.0.0");
+    }
+    #[test]
+    fn ipv4_pool_subnet_tree() {
         let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
-        for i in ips {
-            println!("{:?}", i);
-        }
-        assert_eq!(1, 1);
+        let ret: Vec<Ipv4Pool> = ips.subnet_tree(25, 28).unwrap().collect();
+        assert_eq!(ret.len(), 2 + 4 + 8 + 16);
+        assert_eq!(ret[0].mask.count_ones(), 25);
+        assert_eq!(ret[29].network(), Ipv4Addr::new(192, 168, 1, 240));
+        assert!(ips.subnet_tree(24, 28).is_err());
+        assert!(ips.subnet_tree(28, 25).is_err());
+        assert!(ips.subnet_tree(25, 33).is_err());
     }
     #[test]
-    fn ipv4_pool_new() {
-        let ip = Ipv4Addr::new(192, 168, 1, 1);
-        let ips = Ipv4Pool::new(ip, 24).unwrap();
-        for i in ips {
-            println!("{:?}", i);
+    fn validate_network_mask() {
+        let network = Ipv4Addr::new(192, 168, 1, 0);
+        let ips = validate_network_mask_v4(network, Ipv4Addr::new(255, 255, 255, 0)).unwrap();
+        assert_eq!(ips.network(), network);
+        assert_eq!(ips.mask.count_ones(), 24);
+        let ret = validate_network_mask_v4(network, Ipv4Addr::new(255, 255, 254, 0));
+        assert!(ret.is_err());
+        let ret = validate_network_mask_v4(network, Ipv4Addr::new(255, 0, 255, 0));
+        assert!(ret.is_err());
+        let host = Ipv4Addr::new(192, 168, 1, 1);
+        let ret = validate_network_mask_v4(host, Ipv4Addr::new(255, 255, 255, 255));
+        assert!(ret.is_ok());
+    }
+    #[test]
+    fn cross_pool_len() {
+        let ranges = [
+            ((192, 168, 1, 1), (192, 168, 1, 10)),
+            ((192, 168, 1, 1), (192, 168, 1, 1)),
+            ((192, 168, 1, 200), (192, 168, 3, 7)),
+        ];
+        for ((a, b, c, d), (e, f, g, h)) in ranges {
+            let start = Ipv4Addr::new(a, b, c, d);
+            let end = Ipv4Addr::new(e, f, g, h);
+            let ips = CrossIpv4Pool::new(start, end).unwrap();
+            assert_eq!(ips.len(), ips.to_vec().len());
+            assert_eq!(ips.len(), ips.count());
+        }
+        let start = Ipv4Addr::new(192, 168, 1, 1);
+        let end = Ipv4Addr::new(192, 168, 1, 10);
+        assert_eq!(CrossIpv4Pool::new(start, end).unwrap().len(), 10);
+        let ips = CrossIpv4Pool::new(Ipv4Addr::UNSPECIFIED, Ipv4Addr::BROADCAST).unwrap();
+        assert_eq!(ips.len(), usize::try_from(1u64 << 32).unwrap_or(usize::MAX));
+        for (start, end) in [
+            ("2001:db8::1", "2001:db8::10"),
+            ("2001:db8::1", "2001:db8::1"),
+        ] {
+            let ips = CrossIpv6Pool::new(start.parse().unwrap(), end.parse().unwrap()).unwrap();
+            assert_eq!(ips.len(), ips.to_vec().len());
+            assert_eq!(ips.len(), ips.count());
         }
-        assert_eq!(1, 1);
     }
     #[test]
-    fn ipv4_pool_contain_1() {
+    fn ipv4_pool_boundary_prefixes() {
+        let ips = Ipv4Pool::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap();
+        assert_eq!(ips.mask, 0);
+        assert_eq!(ips.len(), 1 << 32);
+        assert_eq!(ips.size(), 1 << 32);
+        assert_eq!(ips.addresses_remaining(), 1 << 32);
+        assert_eq!(ips.broadcast(), Ipv4Addr::new(255, 255, 255, 255));
+        let mut ips = Ipv4Pool::from("10.0.0.0/0").unwrap();
+        assert_eq!(ips.network(), Ipv4Addr::new(0, 0, 0, 0));
+        assert_eq!(ips.next(), Some(Ipv4Addr::new(0, 0, 0, 0)));
+        assert_eq!(ips.next(), Some(Ipv4Addr::new(0, 0, 0, 1)));
+        assert_eq!(ips.addresses_remaining(), (1 << 32) - 2);
+        let ips = Ipv4Pool::from("200.0.0.0/1").unwrap();
+        assert_eq!(ips.network(), Ipv4Addr::new(128, 0, 0, 0));
+        assert_eq!(ips.len(), 1 << 31);
+        let ips = Ipv4Pool::from("192.168.1.1/32").unwrap();
+        assert_eq!(ips.len(), 1);
+        assert_eq!(ips.count(), 1);
+        let pools = [
+            Ipv4Pool::from("0.0.0.0/1").unwrap(),
+            Ipv4Pool::from("128.0.0.0/1").unwrap(),
+        ];
+        let ret = normalize_ipv4(&pools);
+        assert_eq!(ret.len(), 1);
+        assert_eq!(ret[0].mask, 0);
+    }
+    #[test]
+    fn search_containing() {
+        let pools = [
+            Ipv4Pool::from("10.0.0.0/8").unwrap(),
+            Ipv4Pool::from("172.16.0.0/12").unwrap(),
+            Ipv4Pool::from("192.168.0.0/24").unwrap(),
+            Ipv4Pool::from("192.168.1.0/25").unwrap(),
+            Ipv4Pool::from("192.168.2.0/23").unwrap(),
+        ];
+        let ret = search_containing_ipv4(&pools, Ipv4Addr::new(192, 168, 3, 200)).unwrap();
+        assert_eq!(ret.network(), Ipv4Addr::new(192, 168, 2, 0));
+        let ret = search_containing_ipv4(&pools, Ipv4Addr::new(10, 1, 2, 3)).unwrap();
+        assert_eq!(ret.network(), Ipv4Addr::new(10, 0, 0, 0));
+        assert!(search_containing_ipv4(&pools, Ipv4Addr::new(192, 168, 1, 200)).is_none());
+        assert!(search_containing_ipv4(&pools, Ipv4Addr::new(8, 8, 8, 8)).is_none());
+        assert!(search_containing_ipv4(&[], Ipv4Addr::new(8, 8, 8, 8)).is_none());
+    }
+    #[test]
+    fn ipv4_pool_hosts() {
         let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
-        let ret = ips.contain_from_str("192.168.1.20").unwrap();
-        println!("{:?}", ret);
-        assert_eq!(ret, true);
+        let hosts: Vec<Ipv4Addr> = ips.hosts().collect();
+        assert_eq!(hosts.len(), 254);
+        assert_eq!(hosts[0], Ipv4Addr::new(192, 168, 1, 1));
+        assert_eq!(hosts[253], Ipv4Addr::new(192, 168, 1, 254));
+        let ips = Ipv4Pool::from("192.168.1.0/30").unwrap();
+        assert_eq!(ips.hosts().count(), 2);
+        let ips = Ipv4Pool::from("192.168.1.0/31").unwrap();
+        assert_eq!(ips.hosts().count(), 2);
+        let ips = Ipv4Pool::from("192.168.1.0/32").unwrap();
+        assert_eq!(ips.hosts().count(), 1);
     }
     #[test]
-    fn ipv4_pool_contain_2() {
+    fn ipv4_pool_rows_by_prefix() {
         let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
-        let ret = ips.contain_from_str("10.8.0.20").unwrap();
-        println!("{:?}", ret);
-        assert_eq!(ret, false);
+        let rows = ips.rows_by_prefix(28).unwrap();
+        assert_eq!(rows.len(), 16);
+        for (row, addrs) in &rows {
+            assert_eq!(addrs.len(), 16);
+            assert_eq!(addrs[0], row.network());
+        }
+        assert_eq!(rows[15].1[15], Ipv4Addr::new(192, 168, 1, 255));
+        assert_eq!(ips.rows_by_prefix(24).unwrap().len(), 1);
+        assert!(ips.rows_by_prefix(23).is_err());
     }
     #[test]
-    fn ipv4_pool_network() {
+    fn pool_size_hint() {
+        let mut ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        assert_eq!(ips.size_hint(), (256, Some(256)));
+        ips.by_ref().take(100).for_each(drop);
+        assert_eq!(ips.size_hint(), (156, Some(156)));
+        assert_eq!(ExactSizeIterator::len(&ips), 156);
+        let v: Vec<Ipv4Addr> = ips.collect();
+        assert_eq!(v.len(), 156);
+        assert_eq!(ips.count(), 156);
+        let mut ips = Ipv6Pool::from("2001:db8::/120").unwrap();
+        ips.next();
+        assert_eq!(ips.size_hint(), (255, Some(255)));
+        assert_eq!(ips.count(), 255);
+        let ips = Ipv6Pool::from("2001:db8::/32").unwrap();
+        assert_eq!(ips.size_hint(), (usize::MAX, None));
+    }
+    #[test]
+    fn ipv4_pool_shortest_prefix_excluding() {
+        let ips = Ipv4Pool::from("192.168.1.0/25").unwrap();
+        let addr = Ipv4Addr::new(192, 168, 1, 128);
+        let prefix_len = ips.shortest_prefix_excluding(addr).unwrap();
+        assert_eq!(prefix_len, 25);
+        assert!(!ips.resize(prefix_len).unwrap().contain(addr));
+        assert!(ips.resize(prefix_len - 1).unwrap().contain(addr));
+        let addr = Ipv4Addr::new(192, 168, 1, 1);
+        assert_eq!(ips.shortest_prefix_excluding(addr), Some(32));
+        assert_eq!(ips.shortest_prefix_excluding(ips.network()), None);
+    }
+    #[test]
+    fn ipv4_pool_to_lines() {
+        let ips = Ipv4Pool::from("192.168.1.0/28").unwrap();
+        let ret = ips.to_lines();
+        assert_eq!(ret.lines().count(), ips.len());
+        assert!(ret.starts_with("192.168.1.0\n192.168.1.1\n"));
+        assert!(ret.ends_with("192.168.1.15"));
+    }
+    #[test]
+    fn ipv4_pool_rev() {
+        let ips = Ipv4Pool::from("192.168.1.0/28").unwrap();
+        let mut forward: Vec<Ipv4Addr> = ips.collect();
+        let backward: Vec<Ipv4Addr> = ips.rev().collect();
+        forward.reverse();
+        assert_eq!(forward, backward);
+        let mut ips = Ipv4Pool::from("192.168.1.0/30").unwrap();
+        assert_eq!(ips.next_back(), Some(Ipv4Addr::new(192, 168, 1, 3)));
+        assert_eq!(ips.next(), Some(Ipv4Addr::new(192, 168, 1, 0)));
+        assert_eq!(ips.next_back(), Some(Ipv4Addr::new(192, 168, 1, 2)));
+        assert_eq!(ips.len(), 4);
+        assert_eq!(ips.size_hint(), (1, Some(1)));
+        assert_eq!(ips.next(), Some(Ipv4Addr::new(192, 168, 1, 1)));
+        assert_eq!(ips.next_back(), None);
+        assert_eq!(ips.next(), None);
+    }
+    #[test]
+    fn ip_pool_mixed_list() {
+        let pools: Vec<IpPool> = ["192.168.0.0/24", "2001:db8::/120"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        assert_eq!(pools[0].network(), "192.168.0.0".parse::<IpAddr>().unwrap());
+        assert_eq!(pools[1].network(), "2001:db8::".parse::<IpAddr>().unwrap());
+        for pool in &pools {
+            let first = pool.network();
+            assert!(pool.contain(first));
+            assert_eq!(pool.count(), 256);
+        }
+        assert!(!pools[0].contain("2001:db8::1".parse().unwrap()));
+        assert!("192.168.0.0/33".parse::<IpPool>().is_err());
+        assert!("2001:db8::/129".parse::<IpPool>().is_err());
+    }
+    #[test]
+    fn union_count() {
+        let pools = [
+            Ipv4Pool::from("10.0.0.0/24").unwrap(),
+            Ipv4Pool::from("10.0.0.128/25").unwrap(),
+            Ipv4Pool::from("10.0.1.0/24").unwrap(),
+            Ipv4Pool::from("10.0.1.0/24").unwrap(),
+        ];
+        let naive: u64 = pools.iter().map(|p| p.address_count()).sum();
+        assert_eq!(naive, 896);
+        assert_eq!(union_count_ipv4(&pools), 512);
+        let whole = [Ipv4Pool::from("0.0.0.0/0").unwrap()];
+        assert_eq!(union_count_ipv4(&whole), 1 << 32);
+    }
+    #[test]
+    fn ipv4_pool_network_is_host_of() {
+        let parent = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let child = Ipv4Pool::from("192.168.1.0/26").unwrap();
+        assert!(!child.network_is_host_of(&parent));
+        let child = Ipv4Pool::from("192.168.1.4/30").unwrap();
+        assert!(child.network_is_host_of(&parent));
+        let child = Ipv4Pool::from("192.168.1.255/32").unwrap();
+        assert!(!child.network_is_host_of(&parent));
+        let child = Ipv4Pool::from("192.168.2.0/24").unwrap();
+        assert!(!child.network_is_host_of(&parent));
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
         let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
-        let network = ips.network();
-        let network_2 = Ipv4Addr::new(192, 168, 1, 0);
-        println!("{:?}", network);
-        assert_eq!(network, network_2);
+        let json = serde_json::to_string(&ips).unwrap();
+        assert_eq!(json, r#""192.168.1.0/24""#);
+        let ret: Ipv4Pool = serde_json::from_str(&json).unwrap();
+        assert_eq!(ret.to_vec(), ips.to_vec());
+        let ips = Ipv6Pool::from("2001:db8::/120").unwrap();
+        let json = serde_json::to_string(&ips).unwrap();
+        assert_eq!(json, r#""2001:db8::/120""#);
+        let ret: Ipv6Pool = serde_json::from_str(&json).unwrap();
+        assert_eq!(ret.to_vec(), ips.to_vec());
+        let start = Ipv4Addr::new(192, 168, 1, 1);
+        let end = Ipv4Addr::new(192, 168, 1, 10);
+        let ips = CrossIpv4Pool::new(start, end).unwrap();
+        let json = serde_json::to_string(&ips).unwrap();
+        assert_eq!(json, r#""192.168.1.1-192.168.1.10""#);
+        let ret: CrossIpv4Pool = serde_json::from_str(&json).unwrap();
+        assert_eq!(ret.to_vec(), ips.to_vec());
+        let ips = CrossIpv6Pool::new(
+            "2001:db8::1".parse().unwrap(),
+            "2001:db8::10".parse().unwrap(),
+        )
+        .unwrap();
+        let json = serde_json::to_string(&ips).unwrap();
+        assert_eq!(json, r#""2001:db8::1-2001:db8::10""#);
+        let ret: CrossIpv6Pool = serde_json::from_str(&json).unwrap();
+        assert_eq!(ret.to_vec(), ips.to_vec());
+        assert!(serde_json::from_str::<Ipv4Pool>(r#""192.168.1.0/33""#).is_err());
+        assert!(serde_json::from_str::<Ipv6Pool>(r#""2001:db8::/129""#).is_err());
+        assert!(serde_json::from_str::<CrossIpv4Pool>(r#""192.168.1.10-192.168.1.1""#).is_err());
+        assert!(serde_json::from_str::<CrossIpv4Pool>(r#""192.168.1.10""#).is_err());
     }
     #[test]
-    fn ipv4_pool_broadcast() {
+    fn ipv4_pool_subnets() {
         let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
-        let broadcast = ips.broadcast();
-        let broadcast_2 = Ipv4Addr::new(192, 168, 1, 255);
-        println!("{:?}", broadcast);
-        assert_eq!(broadcast, broadcast_2);
+        let subnets: Vec<Ipv4Pool> = ips.subnets(26).unwrap().collect();
+        assert_eq!(subnets.len(), 4);
+        let networks: Vec<Ipv4Addr> = subnets.iter().map(|p| p.network()).collect();
+        assert_eq!(
+            networks,
+            [0, 64, 128, 192].map(|d| Ipv4Addr::new(192, 168, 1, d))
+        );
+        let ips = Ipv4Pool::from("10.0.0.0/16").unwrap();
+        for new_prefix in [16, 20, 24] {
+            let subnets: Vec<Ipv4Pool> = ips.subnets(new_prefix).unwrap().collect();
+            assert_eq!(subnets.len(), 1 << (new_prefix - 16));
+            let addrs: Vec<Ipv4Addr> = subnets.iter().flat_map(|p| p.to_vec()).collect();
+            assert_eq!(addrs, ips.to_vec());
+        }
+        assert!(ips.subnets(15).is_err());
+        assert!(ips.subnets(33).is_err());
     }
     #[test]
-    fn ipv4_pool_size() {
+    fn cross_ipv4_pool_enclosing_broadcast() {
+        let ranges = [
+            ((192, 168, 1, 1), (192, 168, 3, 254)),
+            ((192, 168, 1, 7), (192, 168, 1, 7)),
+            ((0, 0, 0, 0), (255, 255, 255, 255)),
+        ];
+        for ((a, b, c, d), (e, f, g, h)) in ranges {
+            let start = Ipv4Addr::new(a, b, c, d);
+            let end = Ipv4Addr::new(e, f, g, h);
+            let ips = CrossIpv4Pool::new(start, end).unwrap();
+            assert_eq!(ips.enclosing_broadcast(), ips.enclosing_pool().broadcast());
+        }
+    }
+    #[test]
+    fn prefix_for_address_count() {
+        assert_eq!(prefix_for_address_count_v4(300).unwrap(), 23);
+        assert_eq!(prefix_for_address_count_v4(256).unwrap(), 24);
+        assert_eq!(prefix_for_address_count_v4(1).unwrap(), 32);
+        assert_eq!(prefix_for_address_count_v4(1 << 32).unwrap(), 0);
+        assert!(prefix_for_address_count_v4((1 << 32) + 1).is_err());
+        assert_eq!(prefix_for_address_count_v6(300), 119);
+        assert_eq!(prefix_for_address_count_v6(1 << 64), 64);
+        assert_eq!(prefix_for_address_count_v6(u128::MAX), 0);
+    }
+    #[test]
+    fn pool_supernet() {
         let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
-        let size = ips.size();
-        println!("{:?}", size);
-        assert_eq!(size, 256);
+        let parent = ips.supernet().unwrap();
+        assert_eq!(parent.network(), Ipv4Addr::new(192, 168, 0, 0));
+        assert_eq!(parent.mask.count_ones(), 23);
+        assert!(parent.supernet_of(&ips));
+        assert!(!ips.supernet_of(&parent));
+        let ips = Ipv4Pool::from("128.0.0.0/1").unwrap();
+        let parent = ips.supernet().unwrap();
+        assert_eq!(parent.mask, 0);
+        assert!(parent.supernet().is_none());
+        let ips = Ipv6Pool::from("2001:db8:1::/48").unwrap();
+        let parent = ips.supernet().unwrap();
+        assert_eq!(parent.network(), "2001:db8::".parse::<Ipv6Addr>().unwrap());
+        assert_eq!(parent.mask.count_ones(), 47);
+        assert!(parent.supernet_of(&ips));
+        let ips = Ipv6Pool::from("8000::/1").unwrap();
+        let parent = ips.supernet().unwrap();
+        assert_eq!(parent.mask, 0);
+        assert!(parent.supernet().is_none());
     }
     #[test]
-    fn ipv4_pool_len() {
+    fn cross_ipv4_pool_to_cidrs() {
+        let start = Ipv4Addr::new(192, 168, 1, 1);
+        let end = Ipv4Addr::new(192, 168, 1, 10);
+        let ips = CrossIpv4Pool::new(start, end).unwrap();
+        let cidrs = ips.to_cidrs();
+        let ret: Vec<(Ipv4Addr, u32)> = cidrs
+            .iter()
+            .map(|p| (p.network(), p.mask.count_ones()))
+            .collect();
+        let expect = [(1, 32), (2, 31), (4, 30), (8, 31), (10, 32)]
+            .map(|(d, prefix_len)| (Ipv4Addr::new(192, 168, 1, d), prefix_len));
+        assert_eq!(ret, expect);
+        let ranges = [
+            ((192, 168, 1, 1), (192, 168, 1, 10)),
+            ((10, 0, 0, 0), (10, 0, 255, 255)),
+            ((10, 0, 0, 7), (10, 0, 3, 3)),
+            ((0, 0, 0, 0), (255, 255, 255, 255)),
+        ];
+        for ((a, b, c, d), (e, f, g, h)) in ranges {
+            let start = Ipv4Addr::new(a, b, c, d);
+            let end = Ipv4Addr::new(e, f, g, h);
+            let ips = CrossIpv4Pool::new(start, end).unwrap();
+            let cidrs = ips.to_cidrs();
+            let mut expected = u32::from(start) as u64;
+            for p in &cidrs {
+                assert_eq!(p.prefix as u64, expected);
+                expected += p.address_count();
+            }
+            assert_eq!(expected, u32::from(end) as u64 + 1);
+        }
+    }
+    #[test]
+    fn ipv4_pool_pairs() {
+        let ips = Ipv4Pool::from("10.0.0.0/29").unwrap();
+        let pairs: Vec<(Ipv4Addr, Ipv4Addr)> = ips.pairs().collect();
+        assert_eq!(pairs.len(), 4);
+        assert_eq!(
+            pairs[0],
+            (Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(10, 0, 0, 1))
+        );
+        assert_eq!(
+            pairs[3],
+            (Ipv4Addr::new(10, 0, 0, 6), Ipv4Addr::new(10, 0, 0, 7))
+        );
+        let ips = Ipv4Pool::from("10.0.0.0/31").unwrap();
+        assert_eq!(ips.pairs().count(), 1);
+        let ips = Ipv4Pool::from("10.0.0.0/32").unwrap();
+        assert_eq!(ips.pairs().count(), 0);
+    }
+    #[test]
+    fn pool_netmask_hostmask() {
         let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
-        let size = ips.len();
-        println!("{:?}", size);
-        assert_eq!(size, 254);
+        assert_eq!(ips.netmask(), Ipv4Addr::new(255, 255, 255, 0));
+        assert_eq!(ips.hostmask(), Ipv4Addr::new(0, 0, 0, 255));
+        let ips = Ipv4Pool::from("192.168.1.0/30").unwrap();
+        assert_eq!(ips.netmask(), Ipv4Addr::new(255, 255, 255, 252));
+        assert_eq!(ips.hostmask(), Ipv4Addr::new(0, 0, 0, 3));
+        let ips = Ipv4Pool::from("0.0.0.0/0").unwrap();
+        assert_eq!(ips.netmask(), Ipv4Addr::new(0, 0, 0, 0));
+        assert_eq!(ips.hostmask(), Ipv4Addr::new(255, 255, 255, 255));
+        let ips = Ipv6Pool::from("2001:db8::/32").unwrap();
+        assert_eq!(ips.netmask(), "ffff:ffff::".parse::<Ipv6Addr>().unwrap());
+        let ips = Ipv6Pool::from("::/0").unwrap();
+        assert_eq!(ips.netmask(), Ipv6Addr::UNSPECIFIED);
     }
     #[test]
-    fn test_largest_identical_prefix() {
-        let ipv4_1 = Ipv4::from("192.168.1.136").unwrap();
-        let ipv4_2 = Ipv4::from("192.168.1.192").unwrap();
-        let ret = ipv4_1.largest_identical_prefix(ipv4_2);
-        println!("{}", ret);
+    fn canonicalize() {
+        assert_eq!(
+            canonicalize_cidr("192.168.1.5/24").unwrap(),
+            "192.168.1.0/24"
+        );
+        assert_eq!(canonicalize_cidr("10.1.2.3/8").unwrap(), "10.0.0.0/8");
+        assert_eq!(
+            canonicalize_cidr("2001:DB8::1/32").unwrap(),
+            "2001:db8::/32"
+        );
+        assert_eq!(
+            canonicalize_cidr("2001:0DB8:0000:0000:0000:0000:0000:00FF/120").unwrap(),
+            "2001:db8::/120"
+        );
+        assert!(canonicalize_cidr("192.168.1.5/33").is_err());
+        assert!(canonicalize_cidr("192.168.1.5").is_err());
     }
     #[test]
-    fn test_max_idt() {
-        let a: u32 = 14;
-        let b: u32 = 12;
-        let mut mask = 1;
-        for _ in 0..31 {
-            mask <<= 1;
+    fn pool_prefix_len() {
+        assert_eq!(Ipv4Pool::from("10.0.0.0/12").unwrap().prefix_len(), 12);
+        assert_eq!(Ipv4Pool::from("0.0.0.0/0").unwrap().prefix_len(), 0);
+        assert_eq!(Ipv6Pool::from("2001:db8::/48").unwrap().prefix_len(), 48);
+        assert_eq!(Ipv6Pool::from("2001:db8::1/128").unwrap().prefix_len(), 128);
+    }
+    #[test]
+    fn ipv6_pool_last_address() {
+        let ips = Ipv6Pool::from("2001:db8::/120").unwrap();
+        assert_eq!(
+            ips.last_address(),
+            "2001:db8::ff".parse::<Ipv6Addr>().unwrap()
+        );
+        let ips = Ipv6Pool::from("::/0").unwrap();
+        assert_eq!(ips.last_address(), Ipv6Addr::from(u128::MAX));
+        let ips = Ipv6Pool::from("2001:db8::1/128").unwrap();
+        assert_eq!(ips.last_address(), ips.network());
+    }
+    #[test]
+    fn pool_eq_hash() {
+        let a = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let mut b = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        b.next();
+        b.next();
+        assert_eq!(a, b);
+        assert_ne!(a, Ipv4Pool::from("192.168.1.0/25").unwrap());
+        let set: HashSet<Ipv4Pool> = [a, b].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        let a = Ipv6Pool::from("2001:db8::/120").unwrap();
+        let mut b = Ipv6Pool::from("2001:db8::/120").unwrap();
+        b.next();
+        assert_eq!(a, b);
+        let set: HashSet<Ipv6Pool> = [a, b].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+    #[test]
+    fn pool_ord() {
+        let mut pools: Vec<Ipv4Pool> = [
+            "192.168.1.0/24",
+            "10.0.0.0/8",
+            "192.168.0.0/16",
+            "192.168.1.0/25",
+            "10.0.0.0/16",
+        ]
+        .iter()
+        .map(|s| Ipv4Pool::from(s).unwrap())
+        .collect();
+        pools.sort();
+        let ret: Vec<String> = pools.iter().map(|p| p.to_ip_route_dst()).collect();
+        assert_eq!(
+            ret,
+            [
+                "10.0.0.0/8",
+                "10.0.0.0/16",
+                "192.168.0.0/16",
+                "192.168.1.0/24",
+                "192.168.1.0/25"
+            ]
+        );
+        let mut pools: Vec<Ipv6Pool> = ["2001:db8:1::/48", "2001:db8::/48", "2001:db8::/32"]
+            .iter()
+            .map(|s| Ipv6Pool::from(s).unwrap())
+            .collect();
+        pools.sort();
+        assert_eq!(pools[0], Ipv6Pool::from("2001:db8::/32").unwrap());
+        assert_eq!(pools[2], Ipv6Pool::from("2001:db8:1::/48").unwrap());
+    }
+    #[test]
+    fn pool_intersection() {
+        let a = Ipv4Pool::from("10.0.0.0/8").unwrap();
+        let b = Ipv4Pool::from("10.1.0.0/16").unwrap();
+        let c = Ipv4Pool::from("192.168.0.0/16").unwrap();
+        assert_eq!(a.intersection(&b), Some(b));
+        assert_eq!(b.intersection(&a), Some(b));
+        assert_eq!(a.intersection(&a), Some(a));
+        assert_eq!(a.intersection(&c), None);
+        assert!(a.overlaps(&b));
+        assert!(!b.overlaps(&c));
+    }
+    #[test]
+    fn pool_aggregate() {
+        let parse = |list: &[&str]| -> Vec<Ipv4Pool> {
+            list.iter().map(|s| Ipv4Pool::from(s).unwrap()).collect()
+        };
+        let pools = parse(&["10.0.0.0/24", "192.168.0.0/23"]);
+        assert_eq!(aggregate_ipv4(&pools), pools);
+        let pools = parse(&[
+            "192.168.3.0/24",
+            "192.168.0.0/24",
+            "192.168.2.0/24",
+            "192.168.1.0/24",
+        ]);
+        assert_eq!(aggregate_ipv4(&pools), parse(&["192.168.0.0/22"]));
+        let pools = parse(&["192.168.0.0/23", "192.168.1.0/24", "192.168.2.0/23"]);
+        assert_eq!(aggregate_ipv4(&pools), parse(&["192.168.0.0/22"]));
+    }
+    #[test]
+    fn addr_try_from_ip_addr() {
+        let v4: IpAddr = Ipv4Addr::new(192, 168, 1, 1).into();
+        let v6: IpAddr = "2001:db8::1".parse().unwrap();
+        let ipv4 = Ipv4::try_from(v4).unwrap();
+        assert_eq!(ipv4.to_std(), Ipv4Addr::new(192, 168, 1, 1));
+        assert_eq!(IpAddr::from(ipv4), v4);
+        assert!(Ipv4::try_from(v6).is_err());
+        let ipv6 = Ipv6::try_from(v6).unwrap();
+        assert_eq!(IpAddr::from(ipv6), v6);
+        assert!(Ipv6::try_from(v4).is_err());
+    }
+    #[cfg(feature = "ipnet")]
+    #[test]
+    fn ipnet_round_trip() {
+        let ips = Ipv4Pool::from("192.168.1.0/24").unwrap();
+        let net = ipnet::Ipv4Net::from(ips);
+        assert_eq!(net, "192.168.1.0/24".parse().unwrap());
+        let ret: Ipv4Pool = net.into();
+        assert_eq!(ret, ips);
+        let ips = Ipv6Pool::from("2001:db8::/32").unwrap();
+        let net = ipnet::Ipv6Net::from(ips);
+        assert_eq!(net, "2001:db8::/32".parse().unwrap());
+        let ret: Ipv6Pool = net.into();
+        assert_eq!(ret, ips);
+    }
+    #[test]
+    fn ipv4_scope() {
+        let scope = |s: &str| {
+            let ipv4 = Ipv4::from(s).unwrap();
+            [
+                ipv4.is_private(),
+                ipv4.is_loopback(),
+                ipv4.is_link_local(),
+                ipv4.is_multicast(),
+                ipv4.is_documentation(),
+            ]
+        };
+        assert_eq!(scope("10.1.2.3"), [true, false, false, false, false]);
+        assert_eq!(scope("172.31.255.255"), [true, false, false, false, false]);
+        assert_eq!(scope("172.32.0.0"), [false, false, false, false, false]);
+        assert_eq!(scope("192.168.1.1"), [true, false, false, false, false]);
+        assert_eq!(scope("127.0.0.1"), [false, true, false, false, false]);
+        assert_eq!(scope("169.254.10.1"), [false, false, true, false, false]);
+        assert_eq!(scope("239.255.255.250"), [false, false, false, true, false]);
+        assert_eq!(scope("198.51.100.7"), [false, false, false, false, true]);
+        assert_eq!(scope("8.8.8.8"), [false, false, false, false, false]);
+        for s in [
+            "10.1.2.3",
+            "127.0.0.1",
+            "169.254.10.1",
+            "224.0.0.1",
+            "203.0.113.1",
+        ] {
+            let std_addr: Ipv4Addr = s.parse().unwrap();
+            let ipv4 = Ipv4::from(s).unwrap();
+            assert_eq!(ipv4.is_private(), std_addr.is_private());
+            assert_eq!(ipv4.is_loopback(), std_addr.is_loopback());
+            assert_eq!(ipv4.is_link_local(), std_addr.is_link_local());
+            assert_eq!(ipv4.is_multicast(), std_addr.is_multicast());
+            assert_eq!(ipv4.is_documentation(), std_addr.is_documentation());
         }
-        println!("{}", mask);
-
-        let mut count = 0;
-        for _ in 0..32 {
-            if a & mask != b & mask {
-                break;
-            }
-            count += 1;
-            mask >>= 1;
+    }
+    #[test]
+    fn pool_is_private() {
+        for s in [
+            "10.0.0.0/8",
+            "10.20.0.0/16",
+            "172.31.0.0/24",
+            "192.168.1.128/25",
+        ] {
+            assert!(Ipv4Pool::from(s).unwrap().is_private());
         }
-        println!("{}", count);
+        // straddles the end of 172.16.0.0/12
+        assert!(!Ipv4Pool::from("172.16.0.0/11").unwrap().is_private());
+        assert!(!Ipv4Pool::from("192.168.0.0/15").unwrap().is_private());
+        assert!(!Ipv4Pool::from("8.8.8.0/24").unwrap().is_private());
     }
+    #[cfg(feature = "rand")]
     #[test]
-    // #[should_panic]
-    fn test_github_issues_1() {
-        let _pool1 = Ipv4Pool::from("1.2.3.4/33");
-        let _pool2 = Ipv4Pool::from("1.2.3.4/");
-        let _pool3 = Ipv4Pool::from("nonip/24");
+    fn pool_random_hosts() {
+        let ips = Ipv4Pool::from("192.168.1.0/29").unwrap();
+        let mut rng = rand::rng();
+        let ret: BTreeSet<Ipv4Addr> = ips.random_hosts(&mut rng, 2000).collect();
+        assert!(ret.iter().all(|addr| ips.contain(*addr)));
+        // 8 addresses, each missed by 2000 draws with a probability of about 1e-116
+        assert_eq!(ret.len(), 8);
+        assert!(ret.contains(&ips.network()));
+        assert!(ret.contains(&ips.broadcast()));
+        let ips = Ipv4Pool::from("0.0.0.0/0").unwrap();
+        ips.random_host(&mut rng);
+    }
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn pool_par_iter() {
+        use rayon::prelude::*;
+        let ips = Ipv4Pool::from("10.0.0.0/20").unwrap();
+        let mut ret: Vec<Ipv4Addr> = ips.par_iter().collect();
+        ret.sort();
+        assert_eq!(ret, ips.to_vec());
+        let mut ips = Ipv4Pool::from("10.0.0.0/30").unwrap();
+        ips.next();
+        let ret: Vec<Ipv4Addr> = ips.into_par_iter().collect();
+        assert_eq!(ret, ips.collect::<Vec<Ipv4Addr>>());
+        let ips = Ipv4Pool::from("0.0.0.0/0").unwrap();
+        assert_eq!(ips.par_iter().len(), 1 << 32);
+        let mut ips = Ipv4Pool::from("0.0.0.0/0").unwrap();
+        ips.nth(u32::MAX as usize - 2);
+        let ret: Vec<Ipv4Addr> = ips.into_par_iter().collect();
+        assert_eq!(
+            ret,
+            [Ipv4Addr::new(255, 255, 255, 254), Ipv4Addr::BROADCAST]
+        );
+        ips.nth(1);
+        assert_eq!(ips.into_par_iter().count(), 0);
+    }
+    #[test]
+    fn pool_nth() {
+        let mut ips = Ipv4Pool::from("10.1.0.0/16").unwrap();
+        assert_eq!(ips.nth(1000), Some(Ipv4Addr::new(10, 1, 3, 232)));
+        assert_eq!(ips.next(), Some(Ipv4Addr::new(10, 1, 3, 233)));
+        assert_eq!(ips.addresses_remaining(), 65536 - 1002);
+        assert_eq!(ips.nth_back(1), Some(Ipv4Addr::new(10, 1, 255, 254)));
+        assert_eq!(ips.nth(usize::MAX), None);
+        assert_eq!(ips.next(), None);
+        let mut ips = Ipv4Pool::from("0.0.0.0/0").unwrap();
+        assert_eq!(ips.nth(u32::MAX as usize), Some(Ipv4Addr::BROADCAST));
+        assert_eq!(ips.next(), None);
+        let mut ips = Ipv6Pool::from("2001:db8::/32").unwrap();
+        let ret = ips.nth(1000).unwrap();
+        assert_eq!(ret, "2001:db8::3e8".parse::<Ipv6Addr>().unwrap());
+        let ret = ips.next().unwrap();
+        assert_eq!(ret, "2001:db8::3e9".parse::<Ipv6Addr>().unwrap());
+    }
+    #[test]
+    fn ipv6_pool_size() {
+        let ips = Ipv6Pool::from("2001:db8::/120").unwrap();
+        assert_eq!(ips.size(), 256);
+        assert_eq!(ips.size(), ips.len());
+        let ips = Ipv6Pool::from("2001:db8::/64").unwrap();
+        assert_eq!(ips.size(), usize::MAX);
+        let ips = Ipv6Pool::from("::/0").unwrap();
+        assert_eq!(ips.size(), usize::MAX);
+    }
+    #[test]
+    fn ipv4_pool_size_hint_full() {
+        let mut ips = Ipv4Pool::FULL;
+        match usize::try_from(1u64 << 32) {
+            Ok(n) => assert_eq!(ips.size_hint(), (n, Some(n))),
+            Err(_) => assert_eq!(ips.size_hint(), (usize::MAX, None)),
+        }
+        ips.next();
+        let n = u32::MAX as usize;
+        assert_eq!(ips.size_hint(), (n, Some(n)));
     }
 }